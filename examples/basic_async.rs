@@ -0,0 +1,27 @@
+extern crate env_logger;
+extern crate futures_util;
+extern crate rethink;
+extern crate tokio;
+
+use futures_util::StreamExt;
+use rethink::async_connection::AsyncCursor;
+use rethink::{query as r, AsyncConnection, ConnectOptions, Error};
+use tokio::runtime::Builder;
+
+fn main() -> Result<(), Error> {
+    env_logger::init();
+    let runtime = Builder::new_current_thread().enable_all().build()?;
+    let connection = runtime.block_on(AsyncConnection::connect(
+        ConnectOptions::new("127.0.0.1:28015".parse()?).user("admin"),
+    ))?;
+    let mut cursor: AsyncCursor<String> = connection.run(
+        r::db("default")
+            .table("comment_cursors")
+            .g("n")
+            .map(|x| x.as_string().add("foo")),
+    )?;
+    while let Some(name) = runtime.block_on(cursor.next()) {
+        println!("{:?}", name?);
+    }
+    Ok(())
+}
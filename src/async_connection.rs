@@ -0,0 +1,402 @@
+//! A non-blocking connection for use with tokio.
+//!
+//! The socket is split between two background tasks: a writer, which sends requests queued by
+//! `AsyncConnection::run` and by cursors, and a reader, which routes each response to the cursor
+//! waiting on its token. Queries are built and responses parsed exactly as for `Connection`.
+
+use connection::{parse_response, Complete, CursorState};
+use errors::{Error, ErrorKind, Result};
+use failure::ResultExt;
+use futures_core::Stream;
+use raw::{self, ConnectOptions, GlobalOptions, RawConnection, Token, RESPONSE_HEADER_SIZE};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use serde_json;
+use std::collections::{HashMap, VecDeque};
+use std::future::Future;
+use std::io::{self, ErrorKind as IoErrorKind};
+use std::marker::PhantomData;
+use std::mem;
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, BufReader, ReadBuf};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::task::{self, JoinHandle};
+
+/// A connection whose queries are run concurrently, each yielding an `AsyncCursor`. Cloning it is
+/// cheap and the clones share the same socket.
+#[derive(Clone)]
+pub struct AsyncConnection {
+    next_token: Arc<AtomicU64>,
+    routes: Routes,
+    requests: UnboundedSender<Vec<u8>>,
+}
+
+impl AsyncConnection {
+    /// Connect to a server. The handshake runs on tokio's blocking thread pool, after which the
+    /// connection is handed to its background tasks.
+    pub fn connect<OptionsT: Into<ConnectOptions>>(options: OptionsT) -> Connect {
+        Connect {
+            options: Some(options.into()),
+            handshake: None,
+        }
+    }
+
+    /// Take over an already handshaken `RawConnection`, spawning the background tasks onto the
+    /// current tokio runtime. Panics if called outside a runtime; fails for TLS connections.
+    pub fn from_raw(raw: RawConnection) -> Result<Self> {
+        let (tcp, next_token) = raw.into_tcp()?;
+        tcp.set_nonblocking(true)
+            .context(ErrorKind::Connection("failed to set nonblocking".into()))?;
+        let tcp = TcpStream::from_std(tcp)
+            .context(ErrorKind::Connection("failed to register socket".into()))?;
+        let (read, write) = tcp.into_split();
+
+        let routes = Arc::new(Mutex::new(Some(HashMap::new())));
+        let (requests, queued) = mpsc::unbounded_channel();
+        task::spawn(Reader {
+            read: BufReader::new(read),
+            header: [0u8; RESPONSE_HEADER_SIZE],
+            body: Vec::new(),
+            filled: 0,
+            token: None,
+            routes: routes.clone(),
+        });
+        task::spawn(Writer {
+            write,
+            queued,
+            request: Vec::new(),
+            written: 0,
+            routes: routes.clone(),
+        });
+
+        Ok(AsyncConnection {
+            next_token: Arc::new(AtomicU64::new(next_token.0)),
+            routes,
+            requests,
+        })
+    }
+
+    /// Start a query. The request is queued for the writer task, so this does not wait; the
+    /// returned cursor yields the documents of the result, as `Connection::next` would.
+    pub fn run<PayloadT, QueryT>(&self, query: QueryT) -> Result<AsyncCursor<PayloadT>>
+    where
+        PayloadT: DeserializeOwned,
+        QueryT: Serialize,
+    {
+        self.run_with(query, &GlobalOptions::default())
+    }
+
+    /// Like `run`, with global options such as `read_mode` or `binary_format`.
+    pub fn run_with<PayloadT, QueryT>(
+        &self,
+        query: QueryT,
+        options: &GlobalOptions,
+    ) -> Result<AsyncCursor<PayloadT>>
+    where
+        PayloadT: DeserializeOwned,
+        QueryT: Serialize,
+    {
+        let token = Token(self.next_token.fetch_add(1, Ordering::SeqCst));
+        let mut request = Vec::new();
+        raw::encode_start_request(&mut request, token, query, options)?;
+
+        let (route, responses) = mpsc::unbounded_channel();
+        match *lock(&self.routes) {
+            Some(ref mut routes) => routes.insert(token, route),
+            None => return Err(closed()),
+        };
+        if self.requests.send(request).is_err() {
+            unroute(&self.routes, token);
+            return Err(closed());
+        }
+
+        Ok(AsyncCursor {
+            token,
+            state: CursorState::Waiting,
+            responses,
+            requests: self.requests.clone(),
+            routes: self.routes.clone(),
+            buffer: Vec::new(),
+            documents: VecDeque::new(),
+            _phantom: PhantomData,
+        })
+    }
+
+    /// Whether the connection is still usable. Once the socket fails, every cursor returns an
+    /// error and no more queries can be run.
+    pub fn is_open(&self) -> bool {
+        lock(&self.routes).is_some()
+    }
+}
+
+/// A stream over the documents of a query run on an `AsyncConnection`. Partial batches are paged
+/// through with CONTINUE requests as they are consumed; dropping the cursor early sends a STOP
+/// and discards the responses still in flight.
+pub struct AsyncCursor<PayloadT> {
+    token: Token,
+    state: CursorState,
+    responses: UnboundedReceiver<Vec<u8>>,
+    requests: UnboundedSender<Vec<u8>>,
+    routes: Routes,
+    buffer: Vec<u8>,
+    documents: VecDeque<Range<usize>>,
+    _phantom: PhantomData<fn() -> PayloadT>,
+}
+
+impl<PayloadT: DeserializeOwned> Stream for AsyncCursor<PayloadT> {
+    type Item = Result<PayloadT>;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context) -> Poll<Option<Self::Item>> {
+        let cursor = self.get_mut();
+        loop {
+            if let Some(document) = cursor.documents.pop_front() {
+                return Poll::Ready(Some(
+                    serde_json::from_slice(&cursor.buffer[document])
+                        .context(ErrorKind::UnexpectedResponse)
+                        .map_err(Error::from),
+                ));
+            }
+
+            match cursor.state {
+                CursorState::Complete => {
+                    cursor.state = CursorState::Exhausted;
+                    return Poll::Ready(None);
+                }
+                CursorState::Exhausted => return Poll::Ready(None),
+                CursorState::Partial => {
+                    let request = raw::encode_continue_request(cursor.token).to_vec();
+                    if cursor.requests.send(request).is_err() {
+                        cursor.state = CursorState::Exhausted;
+                        return Poll::Ready(Some(Err(closed())));
+                    }
+                    cursor.state = CursorState::Waiting;
+                }
+                CursorState::Waiting => {}
+            }
+
+            match cursor.responses.poll_recv(context) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => {
+                    cursor.state = CursorState::Exhausted;
+                    return Poll::Ready(Some(Err(closed())));
+                }
+                Poll::Ready(Some(buffer)) => {
+                    cursor.buffer = buffer;
+                    match parse_response(&cursor.buffer, 0, &mut cursor.documents) {
+                        Ok(Complete::Yes) => {
+                            cursor.state = CursorState::Complete;
+                            unroute(&cursor.routes, cursor.token);
+                        }
+                        Ok(Complete::No) => cursor.state = CursorState::Partial,
+                        Err(error) => {
+                            cursor.state = CursorState::Exhausted;
+                            unroute(&cursor.routes, cursor.token);
+                            return Poll::Ready(Some(Err(error)));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl<PayloadT> Drop for AsyncCursor<PayloadT> {
+    fn drop(&mut self) {
+        match self.state {
+            CursorState::Waiting | CursorState::Partial => {
+                debug!("Stopping {:?}.", self.token);
+                unroute(&self.routes, self.token);
+                let _ = self
+                    .requests
+                    .send(raw::encode_stop_request(self.token).to_vec());
+            }
+            CursorState::Complete | CursorState::Exhausted => {}
+        }
+    }
+}
+
+/// The future returned by `AsyncConnection::connect`.
+pub struct Connect {
+    options: Option<ConnectOptions>,
+    handshake: Option<JoinHandle<Result<RawConnection>>>,
+}
+
+impl Future for Connect {
+    type Output = Result<AsyncConnection>;
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<Self::Output> {
+        let connect = self.get_mut();
+        if let Some(options) = connect.options.take() {
+            connect.handshake = Some(task::spawn_blocking(move || RawConnection::connect(options)));
+        }
+        let handshake = connect
+            .handshake
+            .as_mut()
+            .expect("Connect polled after completion.");
+        match Pin::new(handshake).poll(context) {
+            Poll::Pending => Poll::Pending,
+            Poll::Ready(Ok(Ok(raw))) => Poll::Ready(AsyncConnection::from_raw(raw)),
+            Poll::Ready(Ok(Err(error))) => Poll::Ready(Err(error)),
+            Poll::Ready(Err(_)) => Poll::Ready(Err(ErrorKind::Connection(
+                "handshake task failed".into(),
+            ).into())),
+        }
+    }
+}
+
+/// The response channels of running queries, by token. `None` once the connection has failed.
+type Routes = Arc<Mutex<Option<HashMap<Token, UnboundedSender<Vec<u8>>>>>>;
+
+fn lock<'a>(
+    routes: &'a Routes,
+) -> MutexGuard<'a, Option<HashMap<Token, UnboundedSender<Vec<u8>>>>> {
+    routes.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+fn unroute(routes: &Routes, token: Token) {
+    if let Some(ref mut routes) = *lock(routes) {
+        routes.remove(&token);
+    }
+}
+
+/// Marks the connection as failed, closing the response channel of every running query.
+fn close(routes: &Routes, error: &io::Error) {
+    if error.kind() == IoErrorKind::UnexpectedEof {
+        debug!("Closing connection: {}", error);
+    } else {
+        warn!("Closing connection: {}", error);
+    }
+    *lock(routes) = None;
+}
+
+fn closed() -> Error {
+    ErrorKind::Connection("connection closed".into()).into()
+}
+
+struct Reader {
+    read: BufReader<OwnedReadHalf>,
+    header: [u8; RESPONSE_HEADER_SIZE],
+    body: Vec<u8>,
+    filled: usize,
+    /// The token of the response whose body is being read, `None` while reading a header.
+    token: Option<Token>,
+    routes: Routes,
+}
+
+impl Future for Reader {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        let reader = self.get_mut();
+        loop {
+            let result = match reader.token {
+                None => poll_fill(&mut reader.read, context, &mut reader.header, &mut reader.filled),
+                Some(_) => poll_fill(&mut reader.read, context, &mut reader.body, &mut reader.filled),
+            };
+            match result {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(Err(error)) => {
+                    close(&reader.routes, &error);
+                    return Poll::Ready(());
+                }
+                Poll::Ready(Ok(())) => {}
+            }
+
+            reader.filled = 0;
+            match reader.token.take() {
+                None => {
+                    let (token, size) = raw::decode_response_header(&reader.header);
+                    debug!("Header: token={:?} size={}", token, size);
+                    reader.token = Some(token);
+                    reader.body = vec![0u8; size];
+                }
+                Some(token) => {
+                    let body = mem::replace(&mut reader.body, Vec::new());
+                    if let Some(ref mut routes) = *lock(&reader.routes) {
+                        let delivered = routes
+                            .get(&token)
+                            .map_or(false, |route| route.send(body).is_ok());
+                        if !delivered {
+                            debug!("Discarding response for {:?}.", token);
+                            routes.remove(&token);
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn poll_fill<ReadT: AsyncRead + Unpin>(
+    read: &mut ReadT,
+    context: &mut Context,
+    buffer: &mut [u8],
+    filled: &mut usize,
+) -> Poll<io::Result<()>> {
+    while *filled < buffer.len() {
+        let mut read_buffer = ReadBuf::new(&mut buffer[*filled..]);
+        match Pin::new(&mut *read).poll_read(context, &mut read_buffer) {
+            Poll::Pending => return Poll::Pending,
+            Poll::Ready(Err(error)) => return Poll::Ready(Err(error)),
+            Poll::Ready(Ok(())) if read_buffer.filled().is_empty() => {
+                return Poll::Ready(Err(IoErrorKind::UnexpectedEof.into()));
+            }
+            Poll::Ready(Ok(())) => *filled += read_buffer.filled().len(),
+        }
+    }
+    Poll::Ready(Ok(()))
+}
+
+/// Completes once every connection handle and cursor is gone, shutting down the write half of the
+/// socket; the server then closes the connection, which stops the reader.
+struct Writer {
+    write: OwnedWriteHalf,
+    queued: UnboundedReceiver<Vec<u8>>,
+    request: Vec<u8>,
+    written: usize,
+    routes: Routes,
+}
+
+impl Future for Writer {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, context: &mut Context) -> Poll<()> {
+        let writer = self.get_mut();
+        loop {
+            if writer.written < writer.request.len() {
+                let write = Pin::new(&mut writer.write);
+                match write.poll_write(context, &writer.request[writer.written..]) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(0)) => {
+                        close(&writer.routes, &IoErrorKind::WriteZero.into());
+                        return Poll::Ready(());
+                    }
+                    Poll::Ready(Ok(written)) => writer.written += written,
+                    Poll::Ready(Err(error)) => {
+                        close(&writer.routes, &error);
+                        return Poll::Ready(());
+                    }
+                }
+                continue;
+            }
+
+            match writer.queued.poll_recv(context) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None) => {
+                    let write = Pin::new(&mut writer.write);
+                    return write.poll_shutdown(context).map(|_| ());
+                }
+                Poll::Ready(Some(request)) => {
+                    writer.request = request;
+                    writer.written = 0;
+                }
+            }
+        }
+    }
+}
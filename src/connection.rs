@@ -1,13 +1,15 @@
-use super::raw::{RawConnection, Token, Wait};
+use super::raw::{GlobalOptions, RawConnection, Token, Wait};
 use byteorder::{BigEndian, ByteOrder};
 use errors::{ErrorKind, Result, ServerErrorKind};
 use failure::ResultExt;
 use serde::de::DeserializeOwned;
 use serde::Serialize;
-use serde_json;
-use std::collections::HashMap;
+use serde_json::{self, value::RawValue};
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
 use std::str::{self, FromStr};
 use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::sync::mpsc::{self, Receiver, Sender};
 
 pub struct Connection {
     raw: RawConnection,
@@ -15,28 +17,74 @@ pub struct Connection {
     num_resets: usize,
     responses: HashMap<Token, Vec<u8>>,
     buffers: Vec<Vec<u8>>,
+    stopped: HashMap<Token, usize>,
+    abandoned: Receiver<Abandoned>,
+    abandon: Sender<Abandoned>,
 }
 
 impl Connection {
     pub fn from_raw(raw: RawConnection) -> Self {
+        let (abandon, abandoned) = mpsc::channel();
         Connection {
             raw,
             connection_id: ConnectionId::new(),
             num_resets: 0,
             buffers: Vec::new(),
             responses: HashMap::new(),
+            stopped: HashMap::new(),
+            abandoned,
+            abandon,
         }
     }
 
     pub fn run<QueryT: Serialize>(&mut self, query: QueryT) -> Result<Cursor> {
-        Ok(Cursor {
-            token: self.raw.start_request(query)?,
+        self.stop_abandoned()?;
+        let token = self.raw.start_request(query)?;
+        Ok(self.cursor(token))
+    }
+
+    /// Like `run`, with global options such as `read_mode` or `binary_format`.
+    pub fn run_with<QueryT: Serialize>(
+        &mut self,
+        query: QueryT,
+        options: &GlobalOptions,
+    ) -> Result<Cursor> {
+        self.stop_abandoned()?;
+        let token = self.raw.start_request_with(query, options)?;
+        Ok(self.cursor(token))
+    }
+
+    fn cursor(&self, token: Token) -> Cursor {
+        Cursor {
+            token,
             connection_id: self.connection_id,
-            num_resets: 0,
+            num_resets: self.num_resets,
+            state: CursorState::Waiting,
             buffer: None,
             position: 0,
-            exhausted: false,
-        })
+            documents: VecDeque::new(),
+            abandon: self.abandon.clone(),
+        }
+    }
+
+    /// Stop a cursor before it is exhausted, freeing the server-side state of its query. Responses
+    /// still in flight for it are discarded as they arrive.
+    ///
+    /// Dropping an unexhausted cursor has the same effect, except the STOP is only sent on the
+    /// next call to `run`, `next` or `stop`.
+    pub fn stop(&mut self, cursor: &mut Cursor) -> Result<()> {
+        assert_eq!(
+            cursor.connection_id, self.connection_id,
+            "Used a cursor from a different connection."
+        );
+        self.stop_abandoned()?;
+        let state = cursor.state;
+        cursor.state = CursorState::Exhausted;
+        self.release(cursor);
+        if cursor.num_resets == self.num_resets {
+            self.stop_token(cursor.token, state)?;
+        }
+        Ok(())
     }
 
     pub fn is_open(&mut self) -> bool {
@@ -47,6 +95,12 @@ impl Connection {
         self.raw.close()
     }
 
+    /// Read the next document from a cursor.
+    ///
+    /// Atom responses yield their single value. Sequence responses yield one document at a time;
+    /// once a partial batch has been consumed a CONTINUE request is sent for the next one. Returns
+    /// `None` if nothing arrived within `wait` or if the sequence has ended; use
+    /// `Cursor::is_exhausted` to tell the two apart.
     pub fn next<PayloadT: DeserializeOwned>(
         &mut self,
         wait: Wait,
@@ -56,46 +110,123 @@ impl Connection {
             cursor.connection_id, self.connection_id,
             "Used a cursor from a different connection."
         );
-        if cursor.exhausted || cursor.num_resets != self.num_resets {
+        if cursor.state == CursorState::Exhausted || cursor.num_resets != self.num_resets {
             return Err(ErrorKind::ReadFromClosedCursor.into());
         }
+        self.stop_abandoned()?;
 
-        if cursor.buffer.is_none() {
-            cursor.buffer = self.recv(cursor.token, wait)?;
-        }
+        loop {
+            if let Some(document) = cursor.documents.pop_front() {
+                let buffer = cursor.buffer.as_ref().expect("documents without buffer");
+                return Ok(Some(
+                    serde_json::from_slice(&buffer[document])
+                        .context(ErrorKind::UnexpectedResponse)?,
+                ));
+            }
 
-        let mut buffer_exhausted = false;
-        let result = if let Some(buffer) = cursor.buffer.as_mut() {
-            let buffer = &buffer[cursor.position..];
-            let size = BigEndian::read_u32(&buffer) as usize;
-            let content_start = cursor.position + 4;
-            let content_end = content_start + size;
-            buffer_exhausted = content_end == buffer.len();
-            cursor.position = content_end;
-            if size > buffer.len() {
-                Err(ErrorKind::Connection("Buffer underrun.".into()).into())
-            } else {
-                match extract_from_response(&buffer[content_start..content_end]) {
-                    Ok((payload, complete)) => {
-                        if complete == Complete::Yes {
-                            cursor.exhausted = true;
-                        }
-                        Ok(Some(payload))
+            match cursor.state {
+                CursorState::Complete => {
+                    cursor.state = CursorState::Exhausted;
+                    self.release(cursor);
+                    return Ok(None);
+                }
+                CursorState::Partial => {
+                    if let Err(error) = self.raw.continue_request(cursor.token) {
+                        self.broken();
+                        return Err(error);
                     }
-                    Err(error) => Err(error),
+                    cursor.state = CursorState::Waiting;
                 }
+                CursorState::Waiting | CursorState::Exhausted => {}
             }
-        } else {
-            Ok(None)
-        };
 
-        if result.is_err() || buffer_exhausted || cursor.exhausted {
-            let mut buffer = cursor.buffer.take().unwrap();
+            if cursor
+                .buffer
+                .as_ref()
+                .map_or(false, |buffer| cursor.position == buffer.len())
+            {
+                self.release(cursor);
+            }
+            if cursor.buffer.is_none() {
+                match self.recv(cursor.token, wait)? {
+                    Some(buffer) => {
+                        cursor.buffer = Some(buffer);
+                        cursor.position = 0;
+                    }
+                    None => return Ok(None),
+                }
+            }
+
+            let parsed = {
+                let buffer = cursor.buffer.as_ref().unwrap();
+                let buffer = &buffer[cursor.position..];
+                let size = BigEndian::read_u32(&buffer) as usize;
+                if size + 4 > buffer.len() {
+                    Err(ErrorKind::Connection("Buffer underrun.".into()).into())
+                } else {
+                    let content_start = cursor.position + 4;
+                    cursor.position = content_start + size;
+                    parse_response(&buffer[4..size + 4], content_start, &mut cursor.documents)
+                }
+            };
+            match parsed {
+                Ok(Complete::Yes) => cursor.state = CursorState::Complete,
+                Ok(Complete::No) => cursor.state = CursorState::Partial,
+                Err(error) => {
+                    cursor.state = CursorState::Exhausted;
+                    self.release(cursor);
+                    return Err(error);
+                }
+            }
+        }
+    }
+
+    fn release(&mut self, cursor: &mut Cursor) {
+        cursor.documents.clear();
+        cursor.position = 0;
+        if let Some(mut buffer) = cursor.buffer.take() {
+            buffer.clear();
+            reclaim(&mut self.buffers, buffer);
+        }
+    }
+
+    fn stop_abandoned(&mut self) -> Result<()> {
+        while let Ok(abandoned) = self.abandoned.try_recv() {
+            if abandoned.num_resets == self.num_resets {
+                self.stop_token(abandoned.token, abandoned.state)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn stop_token(&mut self, token: Token, state: CursorState) -> Result<()> {
+        // The server answers the STOP itself, as well as any CONTINUE still in flight.
+        let mut num_pending = match state {
+            CursorState::Waiting => 2,
+            CursorState::Partial => 1,
+            CursorState::Complete | CursorState::Exhausted => return Ok(()),
+        };
+        if let Some(mut buffer) = self.responses.remove(&token) {
+            num_pending -= 1;
             buffer.clear();
             reclaim(&mut self.buffers, buffer);
         }
+        debug!("Stopping {:?}, discarding {} responses.", token, num_pending);
+        if let Err(error) = self.raw.stop_request(token) {
+            self.broken();
+            return Err(error);
+        }
+        self.stopped.insert(token, num_pending);
+        Ok(())
+    }
 
-        result
+    fn broken(&mut self) {
+        for (_, mut buffer) in self.responses.drain() {
+            buffer.clear();
+            reclaim(&mut self.buffers, buffer);
+        }
+        self.stopped.clear();
+        self.num_resets += 1;
     }
 
     fn recv(&mut self, token: Token, wait: Wait) -> Result<Option<Vec<u8>>> {
@@ -104,31 +235,54 @@ impl Connection {
         }
 
         let mut buffer = self.buffers.pop().unwrap_or_else(Vec::new);
-        // TODO(cristicbz): wait is incorrect.
-        let response_token = {
-            let responses = &mut self.responses;
-            self.raw.recv(wait, |response_token| {
-                if response_token == token {
-                    &mut buffer
-                } else {
-                    responses.entry(response_token).or_insert_with(Vec::new)
+        let mut discarded = Vec::new();
+        let response_token = loop {
+            // TODO(cristicbz): wait is incorrect.
+            let response_token = {
+                let responses = &mut self.responses;
+                let stopped = &self.stopped;
+                self.raw.recv(wait, |response_token| {
+                    if response_token == token {
+                        &mut buffer
+                    } else if stopped.contains_key(&response_token) {
+                        &mut discarded
+                    } else {
+                        responses.entry(response_token).or_insert_with(Vec::new)
+                    }
+                })
+            };
+
+            // Responses to stopped queries are dropped and we keep waiting for ours instead.
+            match response_token {
+                Ok(Some(response_token)) if self.stopped.contains_key(&response_token) => {
+                    discarded.clear();
+                    let num_pending = {
+                        let num_pending = self.stopped.get_mut(&response_token).unwrap();
+                        *num_pending -= 1;
+                        *num_pending
+                    };
+                    if num_pending == 0 {
+                        self.stopped.remove(&response_token);
+                    }
                 }
-            })
+                response_token => break response_token,
+            }
         };
+        if discarded.capacity() > 0 {
+            reclaim(&mut self.buffers, discarded);
+        }
 
         match response_token {
             Ok(Some(response_token)) if response_token == token => Ok(Some(buffer)),
             Ok(_) => {
+                buffer.clear();
                 reclaim(&mut self.buffers, buffer);
                 Ok(None)
             }
             Err(error) => {
+                buffer.clear();
                 reclaim(&mut self.buffers, buffer);
-                for (_, mut buffer) in self.responses.drain() {
-                    buffer.clear();
-                    reclaim(&mut self.buffers, buffer);
-                }
-                self.num_resets += 1;
+                self.broken();
                 Err(error.into())
             }
         }
@@ -136,14 +290,18 @@ impl Connection {
 }
 
 #[derive(PartialEq, Eq)]
-enum Complete {
+pub(crate) enum Complete {
     Yes,
     No,
 }
 
-fn extract_from_response<PayloadT: DeserializeOwned>(
+/// Parses a single response, pushing the ranges of its documents (relative to `offset`) onto
+/// `documents`.
+pub(crate) fn parse_response(
     buffer: &[u8],
-) -> Result<(PayloadT, Complete)> {
+    offset: usize,
+    documents: &mut VecDeque<Range<usize>>,
+) -> Result<Complete> {
     debug!("Response: {}", String::from_utf8_lossy(buffer));
     if !buffer.starts_with(RESPONSE_PREFIX) {
         return Err(ErrorKind::Connection(
@@ -173,19 +331,25 @@ fn extract_from_response<PayloadT: DeserializeOwned>(
     ).context(ErrorKind::Connection("response type not a number".into()))?;
     match response_type {
         SUCCESS_ATOM => {
-            let response: AtomResponse<PayloadT> =
-                serde_json::from_slice(buffer).context(ErrorKind::UnexpectedResponse)?;
-            Ok((response.payload.0, Complete::Yes))
-        }
-        SUCCESS_PARTIAL => {
-            let response: SequenceResponse<PayloadT> =
+            let response: AtomResponse =
                 serde_json::from_slice(buffer).context(ErrorKind::UnexpectedResponse)?;
-            Ok((response.payload, Complete::No))
+            documents.push_back(document_range(buffer, offset, response.payload.0));
+            Ok(Complete::Yes)
         }
-        SUCCESS_SEQUENCE => {
-            let response: SequenceResponse<PayloadT> =
+        SUCCESS_PARTIAL | SUCCESS_SEQUENCE => {
+            let response: SequenceResponse =
                 serde_json::from_slice(buffer).context(ErrorKind::UnexpectedResponse)?;
-            Ok((response.payload, Complete::Yes))
+            documents.extend(
+                response
+                    .payload
+                    .into_iter()
+                    .map(|document| document_range(buffer, offset, document)),
+            );
+            Ok(if response_type == SUCCESS_PARTIAL {
+                Complete::No
+            } else {
+                Complete::Yes
+            })
         }
         CLIENT_ERROR | RUNTIME_ERROR | COMPILE_ERROR => {
             let response: ErrorResponse = serde_json::from_slice(buffer).context(
@@ -210,6 +374,11 @@ fn extract_from_response<PayloadT: DeserializeOwned>(
     }
 }
 
+fn document_range(buffer: &[u8], offset: usize, document: &RawValue) -> Range<usize> {
+    let start = document.get().as_ptr() as usize - buffer.as_ptr() as usize + offset;
+    start..start + document.get().len()
+}
+
 const RESPONSE_PREFIX: &[u8] = b"{\"t\":";
 const RESPONSE_TYPE_START: usize = 5; // After the prefix.
 
@@ -223,15 +392,15 @@ const COMPILE_ERROR: u32 = 17;
 const RUNTIME_ERROR: u32 = 18;
 
 #[derive(Deserialize)]
-struct AtomResponse<PayloadT> {
-    #[serde(rename = "r")]
-    payload: (PayloadT,),
+struct AtomResponse<'a> {
+    #[serde(rename = "r", borrow)]
+    payload: (&'a RawValue,),
 }
 
 #[derive(Deserialize)]
-struct SequenceResponse<PayloadT> {
-    #[serde(rename = "r")]
-    payload: PayloadT,
+struct SequenceResponse<'a> {
+    #[serde(rename = "r", borrow)]
+    payload: Vec<&'a RawValue>,
 }
 
 #[derive(Deserialize)]
@@ -249,10 +418,54 @@ struct ErrorResponse {
 pub struct Cursor {
     token: Token,
     connection_id: ConnectionId,
-    exhausted: bool,
+    num_resets: usize,
+    state: CursorState,
     buffer: Option<Vec<u8>>,
     position: usize,
+    documents: VecDeque<Range<usize>>,
+    abandon: Sender<Abandoned>,
+}
+
+impl Drop for Cursor {
+    fn drop(&mut self) {
+        match self.state {
+            CursorState::Waiting | CursorState::Partial => {
+                let _ = self.abandon.send(Abandoned {
+                    token: self.token,
+                    num_resets: self.num_resets,
+                    state: self.state,
+                });
+            }
+            CursorState::Complete | CursorState::Exhausted => {}
+        }
+    }
+}
+
+impl Cursor {
+    /// Whether the cursor has returned every document of its result. Reading from an exhausted
+    /// cursor is an error.
+    pub fn is_exhausted(&self) -> bool {
+        self.state == CursorState::Exhausted
+    }
+}
+
+/// A cursor dropped before it was exhausted, queued to be stopped by its connection.
+struct Abandoned {
+    token: Token,
     num_resets: usize,
+    state: CursorState,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum CursorState {
+    /// Waiting for a response to a START or CONTINUE request.
+    Waiting,
+    /// The buffered batch is followed by more, a CONTINUE is needed once it is drained.
+    Partial,
+    /// The buffered batch is the last one.
+    Complete,
+    /// All documents have been returned.
+    Exhausted,
 }
 
 fn reclaim(buffers: &mut Vec<Vec<u8>>, buffer: Vec<u8>) {
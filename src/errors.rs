@@ -22,6 +22,12 @@ pub enum ErrorKind {
     #[fail(display = "Connection error: {}", _0)]
     Connection(Cow<'static, str>),
 
+    #[fail(display = "Authentication error: {}", _0)]
+    Authentication(Cow<'static, str>),
+
+    #[fail(display = "TLS error: {}", _0)]
+    Tls(Cow<'static, str>),
+
     #[fail(
         display = "{} error code={}, span={:?}: {}",
         kind,
@@ -11,21 +11,44 @@ extern crate serde_derive;
 extern crate serde_json;
 
 extern crate arrayvec;
+extern crate base64;
 extern crate byteorder;
+#[cfg(feature = "chrono")]
+extern crate chrono;
+#[cfg(feature = "async")]
+extern crate futures_core;
+extern crate hmac;
+#[cfg(feature = "tls")]
+extern crate native_tls;
+extern crate pbkdf2;
+extern crate rand;
+extern crate sha2;
+#[cfg(feature = "async")]
+extern crate tokio;
 
 #[macro_use]
 extern crate log;
 
 extern crate r2d2;
 
+#[cfg(feature = "async")]
+pub mod async_connection;
 pub mod connection;
 pub mod query;
 pub mod raw;
+pub mod types;
 
 mod enums;
 mod errors;
 mod manager;
+mod scram;
+mod transport;
 
+#[cfg(feature = "async")]
+pub use async_connection::AsyncConnection;
 pub use connection::Connection;
+pub use errors::{ErrorKind, ServerErrorKind};
 pub use failure::Error;
-pub use raw::{RawConnection, Wait};
+pub use raw::{BinaryFormat, ConnectOptions, GlobalOptions, RawConnection, ReadMode, Wait};
+#[cfg(feature = "tls")]
+pub use transport::TlsOptions;
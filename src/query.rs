@@ -2,8 +2,10 @@ pub use failure::Error;
 use arrayvec::ArrayVec;
 use serde::ser::{Serialize, Serializer};
 use std::marker::PhantomData;
-use std::sync::atomic::{AtomicUsize, Ordering, ATOMIC_USIZE_INIT};
+use std::ops;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use super::enums::term;
+use super::types::{Geometry, ReqlBinary, ReqlTime};
 
 
 #[derive(Copy, Clone, Debug)]
@@ -27,7 +29,394 @@ pub fn args<OutT, OfT: IntoExpr<Out=ArrayOut<OutT>>>(of: OfT) -> Args<OutT, Term
     }
 }
 
-// FIXME: Implement binary.
+/// Like `args`, but splices a tuple of expressions with the same output type, e.g. to pass several
+/// operands to a variadic term: `x.le(r::args_of((y, 10)))` tests `x <= y <= 10`.
+pub fn args_of<OfT: IntoArgsTuple>(of: OfT) -> Args<OfT::Item, Term<(OfT::Ast,)>> {
+    Args {
+        ast: term(term::ARGS, (of.into_ast(),)),
+        _phantom: PhantomData,
+    }
+}
+
+/// Replace a nested object in `merge` or `update` instead of merging into it. Use
+/// `literal_empty` to remove a field.
+pub fn literal<ValueT: IntoExpr>(value: ValueT) -> Expr<ValueT::Out, Term<(ValueT::Ast,)>> {
+    Expr::raw(term(term::LITERAL, (value.into_ast(),)))
+}
+
+/// A `literal` with no value, which removes the field it is assigned to in `merge` or `update`.
+pub fn literal_empty() -> Expr<AnyOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::LITERAL, []))
+}
+
+/// Construct an object from a tuple of key-value pairs, e.g.
+/// `r::object((("name", "Ann"), ("joined", r::now())))`.
+pub fn object<PairsT: IntoObjectPairs>(pairs: PairsT) -> Expr<ObjectOut, Term<PairsT::Args>> {
+    Expr::raw(term(term::OBJECT, pairs.into_object_args()))
+}
+
+/// A null standing in for a `NullOr<OfT>`, e.g. as a branch of `r::branch` opposite an
+/// `as_null_or()` value.
+pub fn null<OfT>() -> Expr<NullOr<OfT>, Null> {
+    Expr::raw(Null)
+}
+
+/// Evaluate `test` and return `true_branch` if it's true, `false_branch` otherwise. Both branches
+/// must have the same output type; use `r::error` in a branch to abort the query instead.
+pub fn branch<TestT, TrueT, FalseT>(
+    test: TestT,
+    true_branch: TrueT,
+    false_branch: FalseT,
+) -> Expr<TrueT::Out, Term<(TestT::Ast, TrueT::Ast, FalseT::Ast)>>
+where
+    TestT: IntoExpr,
+    TestT::Out: IsBool,
+    TrueT: IntoExpr,
+    FalseT: IntoExpr<Out = TrueT::Out>,
+{
+    Expr::raw(term(
+        term::BRANCH,
+        (test.into_ast(), true_branch.into_ast(), false_branch.into_ast()),
+    ))
+}
+
+/// Throw a runtime error with the given message. The output type is free, so that it can stand in
+/// for any branch of `r::branch` or result of `default_with`.
+pub fn error<OutT, MessageT>(message: MessageT) -> Expr<OutT, Term<(MessageT::Ast,)>>
+where
+    MessageT: IntoExpr,
+    MessageT::Out: IsString,
+{
+    Expr::raw(term(term::ERROR, (message.into_ast(),)))
+}
+
+/// Generate a stream of all the numbers from zero upwards. Use `limit` to bound it.
+pub fn range_infinite() -> Expr<StreamOut<NumberOut>, Term<[u8; 0]>> {
+    Expr::raw(term(term::RANGE, []))
+}
+
+/// Generate a stream of the numbers from zero up to (but not including) `end`.
+pub fn range<EndT>(end: EndT) -> Expr<StreamOut<NumberOut>, Term<(EndT::Ast,)>>
+where
+    EndT: IntoExpr<Out=NumberOut>,
+{
+    Expr::raw(term(term::RANGE, (end.into_ast(),)))
+}
+
+/// Generate a stream of the numbers from `start` up to (but not including) `end`.
+pub fn range_between<StartT, EndT>(
+    start: StartT,
+    end: EndT,
+) -> Expr<StreamOut<NumberOut>, Term<(StartT::Ast, EndT::Ast)>>
+where
+    StartT: IntoExpr<Out=NumberOut>,
+    EndT: IntoExpr<Out=NumberOut>,
+{
+    Expr::raw(term(term::RANGE, (start.into_ast(), end.into_ast())))
+}
+
+/// Generate a random float between 0 (inclusive) and 1 (exclusive).
+pub fn random() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::RANDOM, []))
+}
+
+/// Generate a random integer between 0 (inclusive) and `max` (exclusive). Accepts the `float`
+/// option, to generate a float in that range instead.
+pub fn random_to<MaxT>(max: MaxT) -> Expr<NumberOut, Term<(MaxT::Ast,), RandomOptions>>
+where
+    MaxT: IntoExpr,
+    MaxT::Out: IsNumber,
+{
+    Expr::raw(term(term::RANDOM, (max.into_ast(),)))
+}
+
+/// Generate a random integer between `min` (inclusive) and `max` (exclusive). Accepts the `float`
+/// option, to generate a float in that range instead.
+pub fn random_between<MinT, MaxT>(
+    min: MinT,
+    max: MaxT,
+) -> Expr<NumberOut, Term<(MinT::Ast, MaxT::Ast), RandomOptions>>
+where
+    MinT: IntoExpr,
+    MinT::Out: IsNumber,
+    MaxT: IntoExpr,
+    MaxT::Out: IsNumber,
+{
+    Expr::raw(term(term::RANDOM, (min.into_ast(), max.into_ast())))
+}
+
+/// Construct a geometry point from a longitude and a latitude.
+pub fn point<LongitudeT, LatitudeT>(
+    longitude: LongitudeT,
+    latitude: LatitudeT,
+) -> Expr<GeometryOut, Term<(LongitudeT::Ast, LatitudeT::Ast)>>
+where
+    LongitudeT: IntoExpr,
+    LongitudeT::Out: IsNumber,
+    LatitudeT: IntoExpr,
+    LatitudeT::Out: IsNumber,
+{
+    Expr::raw(term(term::POINT, (longitude.into_ast(), latitude.into_ast())))
+}
+
+/// Construct a geometry line from an array of two or more points, each given as a point or a
+/// `[longitude, latitude]` array.
+pub fn line<PointT, PointsT>(points: PointsT) -> Expr<GeometryOut, Term<(Term<(PointsT::Ast,)>,)>>
+where
+    PointT: IsPoint,
+    PointsT: IntoExpr<Out=ArrayOut<PointT>>,
+{
+    Expr::raw(term(term::LINE, (args(points).ast,)))
+}
+
+/// Construct a geometry polygon from an array of three or more points, each given as a point or a
+/// `[longitude, latitude]` array.
+pub fn polygon<PointT, PointsT>(
+    points: PointsT,
+) -> Expr<GeometryOut, Term<(Term<(PointsT::Ast,)>,)>>
+where
+    PointT: IsPoint,
+    PointsT: IntoExpr<Out=ArrayOut<PointT>>,
+{
+    Expr::raw(term(term::POLYGON, (args(points).ast,)))
+}
+
+/// Construct a polygon approximating a circle around a point, with a radius in `unit`s (meters by
+/// default). Accepts the `num_vertices`, `geo_system`, `unit` and `fill` options.
+pub fn circle<CenterT, RadiusT>(
+    center: CenterT,
+    radius: RadiusT,
+) -> Expr<GeometryOut, Term<(CenterT::Ast, RadiusT::Ast), CircleOptions>>
+where
+    CenterT: IntoExpr,
+    CenterT::Out: IsPoint,
+    RadiusT: IntoExpr,
+    RadiusT::Out: IsNumber,
+{
+    Expr::raw(term(term::CIRCLE, (center.into_ast(), radius.into_ast())))
+}
+
+/// Convert a GeoJSON object to a geometry.
+pub fn geojson<ObjectT>(geojson: ObjectT) -> Expr<GeometryOut, Term<(ObjectT::Ast,)>>
+where
+    ObjectT: IntoExpr,
+    ObjectT::Out: IsObject,
+{
+    Expr::raw(term(term::GEOJSON, (geojson.into_ast(),)))
+}
+
+/// The time the query was received by the server. `now` is evaluated once per query, so every
+/// call returns the same time.
+pub fn now() -> Expr<TimeOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::NOW, []))
+}
+
+/// Create a time at the start of the given day, in a timezone given as `"[+-]HH:MM"` or `"Z"`.
+pub fn time<YearT, MonthT, DayT, TimezoneT>(
+    year: YearT,
+    month: MonthT,
+    day: DayT,
+    timezone: TimezoneT,
+) -> Expr<TimeOut, Term<(YearT::Ast, MonthT::Ast, DayT::Ast, TimezoneT::Ast)>>
+where
+    YearT: IntoExpr,
+    YearT::Out: IsNumber,
+    MonthT: IntoExpr,
+    MonthT::Out: IsNumber,
+    DayT: IntoExpr,
+    DayT::Out: IsNumber,
+    TimezoneT: IntoExpr,
+    TimezoneT::Out: IsString,
+{
+    Expr::raw(term(
+        term::TIME,
+        (
+            year.into_ast(),
+            month.into_ast(),
+            day.into_ast(),
+            timezone.into_ast(),
+        ),
+    ))
+}
+
+/// Like `time`, but also with the hour, minute and (possibly fractional) second of the day.
+pub fn time_hms<YearT, MonthT, DayT, HoursT, MinutesT, SecondsT, TimezoneT>(
+    year: YearT,
+    month: MonthT,
+    day: DayT,
+    hours: HoursT,
+    minutes: MinutesT,
+    seconds: SecondsT,
+    timezone: TimezoneT,
+) -> Expr<
+    TimeOut,
+    Term<(
+        YearT::Ast,
+        MonthT::Ast,
+        DayT::Ast,
+        HoursT::Ast,
+        MinutesT::Ast,
+        SecondsT::Ast,
+        TimezoneT::Ast,
+    )>,
+>
+where
+    YearT: IntoExpr,
+    YearT::Out: IsNumber,
+    MonthT: IntoExpr,
+    MonthT::Out: IsNumber,
+    DayT: IntoExpr,
+    DayT::Out: IsNumber,
+    HoursT: IntoExpr,
+    HoursT::Out: IsNumber,
+    MinutesT: IntoExpr,
+    MinutesT::Out: IsNumber,
+    SecondsT: IntoExpr,
+    SecondsT::Out: IsNumber,
+    TimezoneT: IntoExpr,
+    TimezoneT::Out: IsString,
+{
+    Expr::raw(term(
+        term::TIME,
+        (
+            year.into_ast(),
+            month.into_ast(),
+            day.into_ast(),
+            hours.into_ast(),
+            minutes.into_ast(),
+            seconds.into_ast(),
+            timezone.into_ast(),
+        ),
+    ))
+}
+
+/// Create a time from the number of seconds since the UNIX epoch, in UTC.
+pub fn epoch_time<SecondsT>(seconds: SecondsT) -> Expr<TimeOut, Term<(SecondsT::Ast,)>>
+where
+    SecondsT: IntoExpr,
+    SecondsT::Out: IsNumber,
+{
+    Expr::raw(term(term::EPOCH_TIME, (seconds.into_ast(),)))
+}
+
+/// Create a time from an ISO 8601 string. Accepts the `default_timezone` option, used if the
+/// string has no timezone of its own.
+pub fn iso8601<StringT>(
+    iso8601: StringT,
+) -> Expr<TimeOut, Term<(StringT::Ast,), Iso8601Options>>
+where
+    StringT: IntoExpr,
+    StringT::Out: IsString,
+{
+    Expr::raw(term(term::ISO8601, (iso8601.into_ast(),)))
+}
+
+/// The days of the week, to compare with `day_of_week`: `r::monday()` is 1.
+pub fn monday() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::MONDAY, []))
+}
+pub fn tuesday() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::TUESDAY, []))
+}
+pub fn wednesday() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::WEDNESDAY, []))
+}
+pub fn thursday() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::THURSDAY, []))
+}
+pub fn friday() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::FRIDAY, []))
+}
+pub fn saturday() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::SATURDAY, []))
+}
+pub fn sunday() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::SUNDAY, []))
+}
+
+/// The months of the year, to compare with `month`: `r::january()` is 1.
+pub fn january() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::JANUARY, []))
+}
+pub fn february() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::FEBRUARY, []))
+}
+pub fn march() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::MARCH, []))
+}
+pub fn april() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::APRIL, []))
+}
+pub fn may() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::MAY, []))
+}
+pub fn june() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::JUNE, []))
+}
+pub fn july() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::JULY, []))
+}
+pub fn august() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::AUGUST, []))
+}
+pub fn september() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::SEPTEMBER, []))
+}
+pub fn october() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::OCTOBER, []))
+}
+pub fn november() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::NOVEMBER, []))
+}
+pub fn december() -> Expr<NumberOut, Term<[u8; 0]>> {
+    Expr::raw(term(term::DECEMBER, []))
+}
+
+/// Sort ascending by a field in `order_by`, or by an index in `order_by_index`.
+pub fn asc<KeyT>(key: KeyT) -> Expr<OrderingOut<StringOut>, Term<(KeyT::Ast,)>>
+where
+    KeyT: IntoExpr,
+    KeyT::Out: IsString,
+{
+    Expr::raw(term(term::ASC, (key.into_ast(),)))
+}
+
+/// Sort descending by a field in `order_by`, or by an index in `order_by_index`.
+pub fn desc<KeyT>(key: KeyT) -> Expr<OrderingOut<StringOut>, Term<(KeyT::Ast,)>>
+where
+    KeyT: IntoExpr,
+    KeyT::Out: IsString,
+{
+    Expr::raw(term(term::DESC, (key.into_ast(),)))
+}
+
+/// Sort ascending by the value of a key function in `order_by`.
+pub fn asc_with<ItemT, ReturnT, FunctionT>(
+    key: FunctionT,
+) -> Expr<OrderingOut<FunctionOut<(ItemT,), ReturnT::Out>>, Term<(FunctionT::FunctionAst,)>>
+where
+    ReturnT: IntoExpr,
+    FunctionT: FnOnce(Var<ItemT>) -> ReturnT + IntoFunctionExpr<(ItemT,), ReturnT::Out>,
+{
+    Expr::raw(term(term::ASC, (key.into_function_expr().ast,)))
+}
+
+/// Sort descending by the value of a key function in `order_by`.
+pub fn desc_with<ItemT, ReturnT, FunctionT>(
+    key: FunctionT,
+) -> Expr<OrderingOut<FunctionOut<(ItemT,), ReturnT::Out>>, Term<(FunctionT::FunctionAst,)>>
+where
+    ReturnT: IntoExpr,
+    FunctionT: FnOnce(Var<ItemT>) -> ReturnT + IntoFunctionExpr<(ItemT,), ReturnT::Out>,
+{
+    Expr::raw(term(term::DESC, (key.into_function_expr().ast,)))
+}
+
+/// Encapsulate binary data, e.g. to store it in a document. Binary values are returned as
+/// `types::ReqlBinary`.
+pub fn binary(bytes: &[u8]) -> Expr<BinaryOut, ReqlBinary> {
+    Expr::raw(ReqlBinary::from(bytes))
+}
 
 /// Reference a database.
 pub fn db<NameT: Serialize>(name: NameT) -> Expr<DbOut, Term<(NameT,)>> {
@@ -96,17 +485,78 @@ impl<OutT, AstT> Expr<OutT, AstT> {
     /// Create a new secondary index on a table. Secondary indexes improve the speed of many read
     /// queries at the slight cost of increased storage space and decreased write performance.
     ///
-    /// FIXME: Index functions and options are not supported just yet, bear with me!
+    /// The index is on the field with the same name as the index; see `index_create_with` and
+    /// `index_create_compound` for other keys. Accepts the `multi` option, to index each element of
+    /// an array separately, and the `geo` option, to create a geospatial index on geometries.
     pub fn index_create<NameT: IntoExpr<Out=StringOut>>(
         self,
         name: NameT,
-    ) -> Expr<ObjectOut, Term<(AstT, NameT::Ast)>>
+    ) -> Expr<ObjectOut, Term<(AstT, NameT::Ast), IndexCreateOptions>>
     where
         OutT: IsTable,
     {
         Expr::raw(term(term::INDEX_CREATE, (self.ast, name.into_ast())))
     }
 
+    /// Create a new secondary index on a table, on the value returned by a function of each
+    /// document. Accepts the same options as `index_create`.
+    pub fn index_create_with<NameT, ReturnT, FunctionT>(
+        self,
+        name: NameT,
+        function: FunctionT,
+    ) -> Expr<ObjectOut, Term<(AstT, NameT::Ast, FunctionT::FunctionAst), IndexCreateOptions>>
+    where
+        OutT: IsTable,
+        NameT: IntoExpr<Out=StringOut>,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<ObjectOut>) -> ReturnT + IntoFunctionExpr<(ObjectOut,), ReturnT::Out>,
+    {
+        Expr::raw(term(
+            term::INDEX_CREATE,
+            (self.ast, name.into_ast(), function.into_function_expr().ast),
+        ))
+    }
+
+    /// Create a new compound secondary index on a table, whose key is the array of the values of
+    /// the given fields (e.g. `["last", "first"]`) of each document. Accepts the same options as
+    /// `index_create`.
+    pub fn index_create_compound<NameT, FieldT, FieldsT>(
+        self,
+        name: NameT,
+        fields: FieldsT,
+    ) -> Expr<
+        ObjectOut,
+        Term<(AstT, NameT::Ast, CompoundIndexFunction<FieldT::Ast>), IndexCreateOptions>,
+    >
+    where
+        OutT: IsTable,
+        NameT: IntoExpr<Out=StringOut>,
+        FieldT: IntoExpr<Out=StringOut>,
+        FieldsT: IntoIterator<Item=FieldT>,
+    {
+        let function = move |document: Var<ObjectOut>| -> Expr<ArrayOut<AnyOut>, _> {
+            Expr::raw(term(
+                term::MAKE_ARRAY,
+                fields
+                    .into_iter()
+                    .map(|field| document.get_field(field).ast)
+                    .collect::<Vec<_>>(),
+            ))
+        };
+        Expr::raw(term(
+            term::INDEX_CREATE,
+            (self.ast, name.into_ast(), function.into_function_expr().ast),
+        ))
+    }
+
+    /// Sets the `geo` option for `index_create`, expects a bool.
+    pub fn with_geo<GeoT>(self, geo: GeoT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<GeoOption, GeoT>,
+    {
+        Expr::raw(self.ast.with_option(geo))
+    }
+
     /// Delete a previously created secondary index of this table.
     pub fn index_drop<NameT: IntoExpr<Out=StringOut>>(
         self,
@@ -130,28 +580,34 @@ impl<OutT, AstT> Expr<OutT, AstT> {
     /// specified as True, a previously existing index with the new name will be deleted and the
     /// index will be renamed. If overwrite is false (the default) an error will be raised if the
     /// new index name already exists.
-    ///
-    /// FIXME: overwrite option is not implemented.
     pub fn index_rename<SourceT: IntoExpr<Out=StringOut>, DestinationT: IntoExpr<Out=StringOut>>(
         self,
         source: SourceT,
         destination: DestinationT,
-    ) -> Expr<ObjectOut, Term<(AstT, SourceT::Ast, DestinationT::Ast)>>
+    ) -> Expr<ObjectOut, Term<(AstT, SourceT::Ast, DestinationT::Ast), IndexRenameOptions>>
     where
         OutT: IsTable,
     {
         Expr::raw(term(
-            term::INDEX_DROP,
+            term::INDEX_RENAME,
             (self.ast, source.into_ast(), destination.into_ast()),
         ))
     }
 
+    /// Sets the `overwrite` option for `index_rename`, expects a bool.
+    pub fn with_overwrite<OverwriteT>(self, overwrite: OverwriteT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<OverwriteOption, OverwriteT>,
+    {
+        Expr::raw(self.ast.with_option(overwrite))
+    }
+
     /// Get the status of the specified indexes on this table, or the status of all indexes on this
-    /// table if no indexes are specified.
+    /// table if no indexes are specified. Each status deserializes into a `types::IndexStatus`.
     pub fn index_status<NameT: IsString, ArgsAstT, KeysT: Into<Args<NameT, ArgsAstT>>>(
         self,
         key: KeysT,
-    ) -> Expr<SelectionOut<ObjectOut>, Term<(AstT, ArgsAstT), GetAllOptions>>
+    ) -> Expr<ArrayOut<IndexStatusOut>, Term<(AstT, ArgsAstT)>>
     where
         OutT: IsTable,
     {
@@ -159,23 +615,25 @@ impl<OutT, AstT> Expr<OutT, AstT> {
     }
 
     /// Wait for the specified indexes on this table to be ready, or for all indexes on this table
-    /// to be ready if no indexes are specified.
+    /// to be ready if no indexes are specified. Returns the same statuses as `index_status`, once
+    /// they are all ready.
     pub fn index_wait<NameT: IsString, ArgsAstT, KeysT: Into<Args<NameT, ArgsAstT>>>(
         self,
         key: KeysT,
-    ) -> Expr<SelectionOut<ObjectOut>, Term<(AstT, ArgsAstT)>>
+    ) -> Expr<ArrayOut<IndexStatusOut>, Term<(AstT, ArgsAstT)>>
     where
         OutT: IsTable,
     {
         Expr::raw(term(term::INDEX_WAIT, (self.ast, key.into().ast)))
     }
 
-    /// Insert documents into a table. Accepts a single document or an array of documents.
-    /// FIXME: Missing insert options.
+    /// Insert documents into a table. Accepts a single document or an array of documents. Accepts
+    /// the `durability`, `return_changes`, `ignore_write_hook` and `conflict` options. The result
+    /// deserializes into a `types::WriteResult`.
     pub fn insert<ObjectsT>(
         self,
         objects: ObjectsT,
-    ) -> Expr<ObjectOut, Term<(AstT, ObjectsT::Ast)>>
+    ) -> Expr<WriteOut, Term<(AstT, ObjectsT::Ast), InsertOptions>>
     where
         ObjectsT: IntoExpr,
         ObjectsT::Out: IsObjectOrObjectSequence,
@@ -184,9 +642,12 @@ impl<OutT, AstT> Expr<OutT, AstT> {
     }
 
     /// Update JSON documents in a table. Accepts a JSON document, a ReQL expression, or a
-    /// combination of the two.
-    /// FIXME: Missing update options.
-    pub fn update<ObjectT>(self, object: ObjectT) -> Expr<ObjectOut, Term<(AstT, ObjectT::Ast)>>
+    /// combination of the two. Accepts the `durability`, `return_changes`, `non_atomic` and
+    /// `ignore_write_hook` options. The result deserializes into a `types::WriteResult`.
+    pub fn update<ObjectT>(
+        self,
+        object: ObjectT,
+    ) -> Expr<WriteOut, Term<(AstT, ObjectT::Ast), UpdateOptions>>
     where
         OutT: IsSelection<ObjectOut>,
         ObjectT: IntoExpr<Out=ObjectOut>,
@@ -195,12 +656,11 @@ impl<OutT, AstT> Expr<OutT, AstT> {
     }
 
     /// Update JSON documents in a table. Accepts a JSON document, a ReQL expression, or a
-    /// combination of the two.
-    /// FIXME: Missing update options.
+    /// combination of the two. Accepts the same options as `update`.
     pub fn update_with<FunctionT, ReturnT>(
         self,
         with: FunctionT,
-    ) -> Expr<ObjectOut, Term<(AstT, FunctionT::FunctionAst)>>
+    ) -> Expr<WriteOut, Term<(AstT, FunctionT::FunctionAst), UpdateOptions>>
     where
         OutT: IsSelection<ObjectOut>,
         ReturnT: IntoExpr<Out=ObjectOut>,
@@ -212,17 +672,121 @@ impl<OutT, AstT> Expr<OutT, AstT> {
         ))
     }
 
-    // FIXME: Implement replace.
+    /// Replace documents in a table with a new document, which must have the same primary key.
+    /// Accepts the same options as `update`. The result deserializes into a `types::WriteResult`.
+    pub fn replace<ObjectT>(
+        self,
+        object: ObjectT,
+    ) -> Expr<WriteOut, Term<(AstT, ObjectT::Ast), UpdateOptions>>
+    where
+        OutT: IsSelection<ObjectOut>,
+        ObjectT: IntoExpr<Out=ObjectOut>,
+    {
+        Expr::raw(term(term::REPLACE, (self.ast, object.into_ast())))
+    }
+
+    /// Replace each document with the result of a function of it. Returning null deletes the
+    /// document, e.g. `r::branch(doc.g("expired").as_bool(), r::null(), doc.as_null_or())`.
+    /// Accepts the same options as `update`.
+    pub fn replace_with<FunctionT, ReturnT>(
+        self,
+        with: FunctionT,
+    ) -> Expr<WriteOut, Term<(AstT, FunctionT::FunctionAst), UpdateOptions>>
+    where
+        OutT: IsSelection<ObjectOut>,
+        ReturnT: IntoExpr,
+        ReturnT::Out: IsReplacement,
+        FunctionT: FnOnce(Var<ObjectOut>) -> ReturnT
+            + IntoFunctionExpr<(ObjectOut,), ReturnT::Out>,
+    {
+        Expr::raw(term(
+            term::REPLACE,
+            (self.ast, with.into_function_expr().ast),
+        ))
+    }
 
-    /// Delete one or more documents from a table.
-    /// FIXME: Missing delete options.
-    pub fn delete(self) -> Expr<ObjectOut, Term<(AstT,)>>
+    /// Delete one or more documents from a table. Accepts the `durability`, `return_changes` and
+    /// `ignore_write_hook` options. The result deserializes into a `types::WriteResult`.
+    pub fn delete(self) -> Expr<WriteOut, Term<(AstT,), DeleteOptions>>
     where
         OutT: IsSelection<ObjectOut>,
     {
         Expr::raw(term(term::DELETE, (self.ast,)))
     }
 
+    /// Sets the `durability` option for a write, expects a string: `"hard"` or `"soft"`,
+    /// overriding the table's durability setting.
+    pub fn with_durability<DurabilityT>(
+        self,
+        durability: DurabilityT,
+    ) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<DurabilityOption, DurabilityT>,
+    {
+        Expr::raw(self.ast.with_option(durability))
+    }
+
+    /// Sets the `return_changes` option for a write: `true` to return the changes made to each
+    /// document, or `"always"` to also return documents which weren't changed.
+    pub fn with_return_changes<ReturnChangesT>(
+        self,
+        return_changes: ReturnChangesT,
+    ) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<ReturnChangesOption, ReturnChangesT>,
+    {
+        Expr::raw(self.ast.with_option(return_changes))
+    }
+
+    /// Sets the `non_atomic` option for `update` and `replace`, expects a bool: whether to allow
+    /// non-deterministic functions, which can't be applied atomically.
+    pub fn with_non_atomic<NonAtomicT>(self, non_atomic: NonAtomicT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<NonAtomicOption, NonAtomicT>,
+    {
+        Expr::raw(self.ast.with_option(non_atomic))
+    }
+
+    /// Sets the `ignore_write_hook` option for a write, expects a bool: whether to skip the
+    /// table's write hook (requires `config` permissions).
+    pub fn with_ignore_write_hook<IgnoreWriteHookT>(
+        self,
+        ignore_write_hook: IgnoreWriteHookT,
+    ) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<IgnoreWriteHookOption, IgnoreWriteHookT>,
+    {
+        Expr::raw(self.ast.with_option(ignore_write_hook))
+    }
+
+    /// Sets the `conflict` option for `insert`: what to do when a document's primary key already
+    /// exists. Takes a strategy name, one of `"error"` (the default), `"replace"` or `"update"`,
+    /// or a resolver function expression; `with_conflict_with` builds one from a closure.
+    pub fn with_conflict<ConflictT>(self, conflict: ConflictT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<ConflictOption, ConflictT>,
+    {
+        Expr::raw(self.ast.with_option(conflict))
+    }
+
+    /// Sets the `conflict` option for `insert` to a resolver function, called with the primary
+    /// key, the old document and the new document; the document it returns is stored.
+    pub fn with_conflict_with<ReturnT, FunctionT>(
+        self,
+        resolver: FunctionT,
+    ) -> Expr<OutT, AstT::WithOption>
+    where
+        ReturnT: IntoExpr<Out=ObjectOut>,
+        FunctionT: FnOnce(Var<AnyOut>, Var<ObjectOut>, Var<ObjectOut>) -> ReturnT
+            + IntoFunctionExpr<(AnyOut, ObjectOut, ObjectOut), ObjectOut>,
+        AstT: WithOption<
+            ConflictOption,
+            Expr<FunctionOut<(AnyOut, ObjectOut, ObjectOut), ObjectOut>, FunctionT::FunctionAst>,
+        >,
+    {
+        Expr::raw(self.ast.with_option(resolver.into_function_expr()))
+    }
+
     /// Ensures that writes on a given table are written to permanent storage. Queries that specify
     /// soft durability (`durability='soft'`) do not give such guarantees, so sync can be used to
     /// ensure the state of these queries. A call to sync does not return until all previous writes
@@ -286,7 +850,8 @@ impl<OutT, AstT> Expr<OutT, AstT> {
         Expr::raw(self.ast.with_option(index))
     }
 
-    /// Sets the `left_bound` option for `between`, expects a string: "closed" or "open".
+    /// Sets the `left_bound` option for `between` and `during`, expects a string: "closed" or
+    /// "open".
     pub fn with_left_bound<BoundT>(self, bound: BoundT) -> Expr<OutT, AstT::WithOption>
     where
         AstT: WithOption<LeftBoundOption, BoundT>,
@@ -294,7 +859,8 @@ impl<OutT, AstT> Expr<OutT, AstT> {
         Expr::raw(self.ast.with_option(bound))
     }
 
-    /// Sets the `right_bound` option for `between`, expects a string: "closed" or "open".
+    /// Sets the `right_bound` option for `between` and `during`, expects a string: "closed" or
+    /// "open".
     pub fn with_right_bound<BoundT>(self, bound: BoundT) -> Expr<OutT, AstT::WithOption>
     where
         AstT: WithOption<RightBoundOption, BoundT>,
@@ -302,6 +868,77 @@ impl<OutT, AstT> Expr<OutT, AstT> {
         Expr::raw(self.ast.with_option(bound))
     }
 
+    /// Sets the `default_timezone` option for `r::iso8601`, expects a string: the timezone to
+    /// use when the ISO 8601 string has none.
+    pub fn with_default_timezone<TimezoneT>(
+        self,
+        timezone: TimezoneT,
+    ) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<DefaultTimezoneOption, TimezoneT>,
+    {
+        Expr::raw(self.ast.with_option(timezone))
+    }
+
+    /// Turn a query into a changefeed, an infinite stream of objects representing changes to the
+    /// query's results as they occur. Works on tables, selections, single documents and
+    /// `order_by_index(...).limit(...)`. Each
+    /// change can be read as a `types::Change<T>`, holding the `old_val` and `new_val` of a
+    /// document; read the cursor with `Wait::Forever`, since a feed may go quiet for longer than
+    /// the message timeout. Accepts the `squash`, `include_initial`, `include_states`,
+    /// `include_types` and `changefeed_queue_size` options.
+    pub fn changes(self) -> Expr<StreamOut<ObjectOut>, Term<(AstT,), ChangesOptions>>
+    where
+        OutT: IsChangefeedSource,
+    {
+        Expr::raw(term(term::CHANGES, (self.ast,)))
+    }
+
+    /// Sets the `squash` option for `changes`: `true` to squash all changes between reads into
+    /// one, or a number of seconds to wait and squash changes for before sending them.
+    pub fn with_squash<SquashT>(self, squash: SquashT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<SquashOption, SquashT>,
+    {
+        Expr::raw(self.ast.with_option(squash))
+    }
+
+    /// Sets the `include_initial` option for `changes`, expects a bool: whether to start the feed
+    /// with the current contents of the query's results, as changes with no `old_val`.
+    pub fn with_include_initial<IncludeT>(self, include: IncludeT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<IncludeInitialOption, IncludeT>,
+    {
+        Expr::raw(self.ast.with_option(include))
+    }
+
+    /// Sets the `include_states` option for `changes`, expects a bool: whether to send
+    /// `{"state": ...}` notes as the feed goes from initializing to ready.
+    pub fn with_include_states<IncludeT>(self, include: IncludeT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<IncludeStatesOption, IncludeT>,
+    {
+        Expr::raw(self.ast.with_option(include))
+    }
+
+    /// Sets the `include_types` option for `changes`, expects a bool: whether to add a `type`
+    /// field to each change (`"add"`, `"remove"`, `"change"`, `"initial"` etc.).
+    pub fn with_include_types<IncludeT>(self, include: IncludeT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<IncludeTypesOption, IncludeT>,
+    {
+        Expr::raw(self.ast.with_option(include))
+    }
+
+    /// Sets the `changefeed_queue_size` option for `changes`, expects a number: how many changes
+    /// the server buffers before dropping some and reporting an error (100,000 by default).
+    pub fn with_changefeed_queue_size<SizeT>(self, size: SizeT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<ChangefeedQueueSizeOption, SizeT>,
+    {
+        Expr::raw(self.ast.with_option(size))
+    }
+
     /// Return all the elements in a sequence for which the given predicate is true. The return
     /// value of filter will be the same as the input (sequence, stream, or array). Documents can be
     /// filtered in a variety of ways—ranges, nested values, boolean conditions, and the results of
@@ -326,34 +963,159 @@ impl<OutT, AstT> Expr<OutT, AstT> {
         ))
     }
 
-    // FIXME: Implement innerJoin
-    // FIXME: Implement outerJoin
-    // FIXME: Implement eqJoin
-
-    /// Used to 'zip' up the result of a join by merging the 'right' fields into 'left' fields of
-    /// each member of the sequence.
-    pub fn zip(self) -> Expr<ObjectOut, Term<(AstT,)>>
+    /// Returns a sequence of `left`/`right` pairs for every element of this sequence and of
+    /// `other` for which the predicate returns true. The predicate is evaluated for every pair, so
+    /// prefer `eq_join` when joining on an indexed field.
+    pub fn inner_join<OtherT, ReturnT, PredicateT>(
+        self,
+        other: OtherT,
+        predicate: PredicateT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, OtherT::Ast, PredicateT::FunctionAst)>>
     where
-        OutT: IsSequence,
-        OutT::SequenceItem: IsObject,
+        OutT: Rebind<JoinOut<<OutT as IsSequence>::SequenceItem, <OtherT::Out as IsSequence>::SequenceItem>>,
+        OtherT: IntoExpr,
+        OtherT::Out: IsSequence,
+        ReturnT: IntoExpr,
+        ReturnT::Out: IsBool,
+        PredicateT: FnOnce(
+            Var<<OutT as IsSequence>::SequenceItem>,
+            Var<<OtherT::Out as IsSequence>::SequenceItem>,
+        ) -> ReturnT
+            + IntoFunctionExpr<
+                (<OutT as IsSequence>::SequenceItem, <OtherT::Out as IsSequence>::SequenceItem),
+                BoolOut,
+            >,
     {
-        Expr::raw(term(term::ZIP, (self.ast,)))
+        Expr::raw(term(
+            term::INNER_JOIN,
+            (self.ast, other.into_ast(), predicate.into_function_expr().ast),
+        ))
     }
 
-    /// Transform each element of one or more sequences by applying a mapping function to them. If
-    /// map is run with two or more sequences, it will iterate for as many items as there are in the
-    /// shortest sequence.
-    ///
-    /// FIXME: Implement support for multiple sequences.
-    pub fn map<ReturnT, MapT>(
+    /// Like `inner_join`, but elements of this sequence which match nothing in `other` are still
+    /// returned, on their own, so the `right` side of each pair may be missing.
+    pub fn outer_join<OtherT, ReturnT, PredicateT>(
         self,
-        map: MapT,
-    ) -> Expr<OutT::Rebound, Term<(AstT, MapT::FunctionAst)>>
+        other: OtherT,
+        predicate: PredicateT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, OtherT::Ast, PredicateT::FunctionAst)>>
     where
-        OutT: Rebind<ReturnT::Out>,
+        OutT: Rebind<
+            OuterJoinOut<
+                <OutT as IsSequence>::SequenceItem,
+                <OtherT::Out as IsSequence>::SequenceItem,
+            >,
+        >,
+        OtherT: IntoExpr,
+        OtherT::Out: IsSequence,
         ReturnT: IntoExpr,
-        MapT: FnOnce(Var<OutT::SequenceItem>) -> ReturnT
-            + IntoFunctionExpr<(OutT::SequenceItem,), ReturnT::Out>,
+        ReturnT::Out: IsBool,
+        PredicateT: FnOnce(
+            Var<<OutT as IsSequence>::SequenceItem>,
+            Var<<OtherT::Out as IsSequence>::SequenceItem>,
+        ) -> ReturnT
+            + IntoFunctionExpr<
+                (<OutT as IsSequence>::SequenceItem, <OtherT::Out as IsSequence>::SequenceItem),
+                BoolOut,
+            >,
+    {
+        Expr::raw(term(
+            term::OUTER_JOIN,
+            (self.ast, other.into_ast(), predicate.into_function_expr().ast),
+        ))
+    }
+
+    /// Join this sequence with a table, pairing each element with the document whose primary key
+    /// (or `index`, if set) equals the element's value for the given field. Elements with no match
+    /// are dropped. Accepts the `index` and `ordered` options.
+    pub fn eq_join<FieldT, TableT>(
+        self,
+        field: FieldT,
+        table: TableT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, FieldT::Ast, TableT::Ast), EqJoinOptions>>
+    where
+        OutT: Rebind<JoinOut<<OutT as IsSequence>::SequenceItem, ObjectOut>>,
+        FieldT: IntoExpr,
+        FieldT::Out: IsString,
+        TableT: IntoExpr,
+        TableT::Out: IsTable,
+    {
+        Expr::raw(term(
+            term::EQ_JOIN,
+            (self.ast, field.into_ast(), table.into_ast()),
+        ))
+    }
+
+    /// Like `eq_join`, but joins on the value returned by a function of each element.
+    pub fn eq_join_with<ReturnT, FunctionT, TableT>(
+        self,
+        key: FunctionT,
+        table: TableT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, FunctionT::FunctionAst, TableT::Ast), EqJoinOptions>>
+    where
+        OutT: Rebind<JoinOut<<OutT as IsSequence>::SequenceItem, ObjectOut>>,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<<OutT as IsSequence>::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(<OutT as IsSequence>::SequenceItem,), ReturnT::Out>,
+        TableT: IntoExpr,
+        TableT::Out: IsTable,
+    {
+        Expr::raw(term(
+            term::EQ_JOIN,
+            (self.ast, key.into_function_expr().ast, table.into_ast()),
+        ))
+    }
+
+    /// Sets the `ordered` option for `eq_join`, expects a bool: whether to return the pairs in
+    /// the order of this sequence, rather than in whatever order is fastest.
+    pub fn with_ordered<OrderedT>(self, ordered: OrderedT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<OrderedOption, OrderedT>,
+    {
+        Expr::raw(self.ast.with_option(ordered))
+    }
+
+    /// Used to 'zip' up the result of a join by merging the 'right' fields into 'left' fields of
+    /// each member of the sequence.
+    pub fn zip(self) -> Expr<OutT::Rebound, Term<(AstT,)>>
+    where
+        OutT: Rebind<ObjectOut>,
+        <OutT as IsSequence>::SequenceItem: IsJoin,
+    {
+        Expr::raw(term(term::ZIP, (self.ast,)))
+    }
+
+    /// The left side of a pair produced by a join.
+    pub fn left(self) -> Expr<OutT::Left, Term<(AstT, &'static str)>>
+    where
+        OutT: IsJoin,
+    {
+        Expr::raw(term(term::GET_FIELD, (self.ast, "left")))
+    }
+
+    /// The right side of a pair produced by a join, a `NullOr` for `outer_join`. The server omits
+    /// `right` for unmatched `outer_join` rows, so there a missing field evaluates to null.
+    pub fn right(self) -> Expr<OutT::Right, OutT::RightAst>
+    where
+        OutT: JoinRight<AstT>,
+    {
+        Expr::raw(OutT::right_ast(term(term::GET_FIELD, (self.ast, "right"))))
+    }
+
+    /// Transform each element of one or more sequences by applying a mapping function to them. If
+    /// map is run with two or more sequences, it will iterate for as many items as there are in the
+    /// shortest sequence.
+    ///
+    /// FIXME: Implement support for multiple sequences.
+    pub fn map<ReturnT, MapT>(
+        self,
+        map: MapT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, MapT::FunctionAst)>>
+    where
+        OutT: Rebind<ReturnT::Out>,
+        ReturnT: IntoExpr,
+        MapT: FnOnce(Var<OutT::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(OutT::SequenceItem,), ReturnT::Out>,
     {
         Expr::raw(term(term::MAP, (self.ast, map.into_function_expr().ast)))
     }
@@ -379,366 +1141,1432 @@ impl<OutT, AstT> Expr<OutT, AstT> {
         concat_map: MapT,
     ) -> Expr<OutT::Rebound, Term<(AstT, MapT::FunctionAst)>>
     where
-        OutT: Rebind<<ReturnT::Out as IsSequence>::SequenceItem>,
-        ReturnT: IntoExpr,
-        ReturnT::Out: IsSequence,
-        MapT: FnOnce(Var<OutT::SequenceItem>) -> ReturnT
-            + IntoFunctionExpr<(OutT::SequenceItem,), ReturnT::Out>,
+        OutT: Rebind<<ReturnT::Out as IsSequence>::SequenceItem>,
+        ReturnT: IntoExpr,
+        ReturnT::Out: IsSequence,
+        MapT: FnOnce(Var<OutT::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(OutT::SequenceItem,), ReturnT::Out>,
+    {
+        Expr::raw(term(
+            term::CONCAT_MAP,
+            (self.ast, concat_map.into_function_expr().ast),
+        ))
+    }
+
+    /// Sort the sequence by one or more keys: field names, `r::asc`/`r::desc` orderings, or key
+    /// functions turned into orderings with `r::asc_with`/`r::desc_with`. Several keys are given as
+    /// a tuple, e.g. `order_by((r::desc("score"), "name"))`. The whole sequence is loaded in
+    /// memory and sorted into an array; to sort a table lazily use `order_by_index`.
+    pub fn order_by<KeysT>(
+        self,
+        keys: KeysT,
+    ) -> Expr<ArrayOut<OutT::SequenceItem>, Term<KeysT::Args>>
+    where
+        OutT: IsSequence,
+        KeysT: IntoOrderKeys<OutT::SequenceItem, AstT>,
+    {
+        Expr::raw(term(term::ORDER_BY, keys.into_order_args(self.ast)))
+    }
+
+    /// Sort the sequence in ascending order of the value returned by a key function.
+    pub fn order_by_with<ReturnT, FunctionT>(
+        self,
+        key: FunctionT,
+    ) -> Expr<ArrayOut<OutT::SequenceItem>, Term<(AstT, FunctionT::FunctionAst)>>
+    where
+        OutT: IsSequence,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<OutT::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(OutT::SequenceItem,), ReturnT::Out>,
+    {
+        Expr::raw(term(
+            term::ORDER_BY,
+            (self.ast, key.into_function_expr().ast),
+        ))
+    }
+
+    /// Sort a table by a secondary (or the primary) index, given by name or wrapped in
+    /// `r::asc`/`r::desc`. Unlike `order_by`, this returns a lazy selection which can be written
+    /// to, or limited and then watched with `changes`.
+    pub fn order_by_index<IndexT>(
+        self,
+        index: IndexT,
+    ) -> Expr<
+        OrderedSelectionOut<ObjectOut>,
+        Term<(AstT,), OrderByOptions<Expr<IndexT::Out, IndexT::Ast>>>,
+    >
+    where
+        OutT: IsTable,
+        IndexT: IntoExpr,
+        IndexT::Out: IsIndexOrdering,
+    {
+        Expr::raw(term::<_, OrderByOptions>(term::ORDER_BY, (self.ast,)).with_option(index))
+    }
+
+    /// Skip a number of elements from the head of the sequence.
+    pub fn skip<NumT>(self, n: NumT) -> Expr<OutT, Term<(AstT, NumT::Ast)>>
+    where
+        NumT: IntoExpr<Out=NumberOut>,
+        OutT: IsSequence,
+    {
+        Expr::raw(term(term::SKIP, (self.ast, n.into_ast())))
+    }
+
+    /// End the sequence after the given number of elements.
+    pub fn limit<NumT>(self, n: NumT) -> Expr<OutT::Limited, Term<(AstT, NumT::Ast)>>
+    where
+        NumT: IntoExpr<Out=NumberOut>,
+        OutT: Limit,
+    {
+        Expr::raw(term(term::LIMIT, (self.ast, n.into_ast())))
+    }
+
+    /// Return the elements of a sequence within the specified range.
+    pub fn slice_after<NumT>(self, start: NumT) -> Expr<OutT, Term<(AstT, NumT::Ast)>>
+    where
+        NumT: IntoExpr<Out=NumberOut>,
+        OutT: IsSequence,
+    {
+        Expr::raw(term(term::SLICE, (self.ast, start.into_ast())))
+    }
+
+    /// Return the elements of a sequence within the specified range.
+    /// FIXME: Add left_bound and right_bound options.
+    pub fn slice<StartT, EndT>(
+        self,
+        start: StartT,
+        end: EndT,
+    ) -> Expr<OutT, Term<(AstT, StartT::Ast, EndT::Ast)>>
+    where
+        StartT: IntoExpr<Out=NumberOut>,
+        EndT: IntoExpr<Out=NumberOut>,
+        OutT: IsSequence,
+    {
+        Expr::raw(term(
+            term::SLICE,
+            (self.ast, start.into_ast(), end.into_ast()),
+        ))
+    }
+
+    /// Get the nth element of a sequence, counting from zero. If the argument is negative, count
+    /// from the last element.
+    pub fn nth<NumT>(self, n: NumT) -> Expr<OutT::Select, Term<(AstT, NumT::Ast)>>
+    where
+        NumT: IntoExpr<Out=NumberOut>,
+        OutT: IsSequence,
+    {
+        Expr::raw(term(term::NTH, (self.ast, n.into_ast())))
+    }
+
+    // FIXME: Implement offsets_of.
+
+    // FIXME: Implement is_empty.
+    pub fn is_empty(self) -> Expr<BoolOut, Term<(AstT,)>>
+    where
+        OutT: IsSequence,
+    {
+        Expr::raw(term(term::IS_EMPTY, (self.ast,)))
+    }
+
+    // FIXME: Implement union.
+    // FIXME: Implement sample.
+
+    /// Group the elements of a sequence by the value of one or more fields (several fields are
+    /// given with `r::args`, and group by an array of their values). Operations chained after
+    /// `group` (`map`, `filter`, `reduce`, `count`, ...) act on each group, giving one result per
+    /// group; `ungroup` turns the groups back into an array. Accepts the `multi` option.
+    pub fn group<FieldT, ArgsAstT, FieldsT>(
+        self,
+        fields: FieldsT,
+    ) -> Expr<GroupedStreamOut<AnyOut, OutT::SequenceItem>, Term<(AstT, ArgsAstT), GroupOptions>>
+    where
+        OutT: IsSequence,
+        FieldT: IsString,
+        FieldsT: Into<Args<FieldT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::GROUP, (self.ast, fields.into().ast)))
+    }
+
+    /// Group the elements of a sequence by the value returned by a function. Accepts the `multi`
+    /// option.
+    pub fn group_with<ReturnT, FunctionT>(
+        self,
+        group: FunctionT,
+    ) -> Expr<
+        GroupedStreamOut<ReturnT::Out, OutT::SequenceItem>,
+        Term<(AstT, FunctionT::FunctionAst), GroupOptions>,
+    >
+    where
+        OutT: IsSequence,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<OutT::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(OutT::SequenceItem,), ReturnT::Out>,
+    {
+        Expr::raw(term(
+            term::GROUP,
+            (self.ast, group.into_function_expr().ast),
+        ))
+    }
+
+    /// Group the documents of a table by the value of a secondary index. Accepts the `multi`
+    /// option.
+    pub fn group_by_index<NameT>(
+        self,
+        index: NameT,
+    ) -> Expr<
+        GroupedStreamOut<AnyOut, ObjectOut>,
+        Term<(AstT,), GroupOptions<Expr<NameT::Out, NameT::Ast>>>,
+    >
+    where
+        OutT: IsTable,
+        NameT: IntoExpr,
+        NameT::Out: IsString,
+    {
+        Expr::raw(term::<_, GroupOptions>(term::GROUP, (self.ast,)).with_option(index))
+    }
+
+    /// Sets the `multi` option for `group` and `index_create`, expects a bool. If true, a grouping
+    /// or index value which is an array puts the element in one group (or under one index key) per
+    /// element of the array.
+    pub fn with_multi<MultiT>(self, multi: MultiT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<MultiOption, MultiT>,
+    {
+        Expr::raw(self.ast.with_option(multi))
+    }
+
+    /// Take a grouped stream or grouped data and turn it into an array of objects representing the
+    /// groups, each with a `group` and a `reduction` field.
+    pub fn ungroup(self) -> Expr<ArrayOut<ObjectOut>, Term<(AstT,)>>
+    where
+        OutT: IsGrouped,
+    {
+        Expr::raw(term(term::UNGROUP, (self.ast,)))
+    }
+
+    /// Produce a single value from a sequence by repeatedly calling the reduction function on
+    /// pairs of elements (or previous results) until only one is left. The function may be called
+    /// in any order, so it should be associative and commutative.
+    pub fn reduce<ReturnT, FunctionT>(
+        self,
+        reduce: FunctionT,
+    ) -> Expr<OutT::Aggregated, Term<(AstT, FunctionT::FunctionAst)>>
+    where
+        OutT: Aggregate<ReturnT::Out>,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<OutT::SequenceItem>, Var<OutT::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(OutT::SequenceItem, OutT::SequenceItem), ReturnT::Out>,
+    {
+        Expr::raw(term(
+            term::REDUCE,
+            (self.ast, reduce.into_function_expr().ast),
+        ))
+    }
+
+    /// Apply a function to a sequence in order, maintaining state via an accumulator which starts
+    /// at `base`. The result is the final value of the accumulator.
+    pub fn fold<BaseT, ReturnT, FunctionT>(
+        self,
+        base: BaseT,
+        combine: FunctionT,
+    ) -> Expr<OutT::Aggregated, Term<(AstT, BaseT::Ast, FunctionT::FunctionAst)>>
+    where
+        OutT: Aggregate<BaseT::Out>,
+        BaseT: IntoExpr,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<BaseT::Out>, Var<OutT::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(BaseT::Out, OutT::SequenceItem), ReturnT::Out>,
+    {
+        Expr::raw(term(
+            term::FOLD,
+            (self.ast, base.into_ast(), combine.into_function_expr().ast),
+        ))
+    }
+
+    /// Like `fold`, but produces a sequence: the `emit` function is called with the old
+    /// accumulator, the element and the new accumulator, and the arrays it returns are
+    /// concatenated into the result. Accepts the `final_emit` option.
+    pub fn fold_emit<BaseT, ReturnT, FunctionT, EmitReturnT, EmitT>(
+        self,
+        base: BaseT,
+        combine: FunctionT,
+        emit: EmitT,
+    ) -> Expr<
+        OutT::Rebound,
+        Term<
+            (AstT, BaseT::Ast, FunctionT::FunctionAst),
+            FoldOptions<
+                BaseT::Out,
+                Expr<
+                    FunctionOut<(BaseT::Out, OutT::SequenceItem, BaseT::Out), EmitReturnT::Out>,
+                    EmitT::FunctionAst,
+                >,
+            >,
+        >,
+    >
+    where
+        OutT: Rebind<<EmitReturnT::Out as IsSequence>::SequenceItem>,
+        BaseT: IntoExpr,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<BaseT::Out>, Var<OutT::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(BaseT::Out, OutT::SequenceItem), ReturnT::Out>,
+        EmitReturnT: IntoExpr,
+        EmitReturnT::Out: IsSequence,
+        EmitT: FnOnce(Var<BaseT::Out>, Var<OutT::SequenceItem>, Var<BaseT::Out>) -> EmitReturnT
+            + IntoFunctionExpr<(BaseT::Out, OutT::SequenceItem, BaseT::Out), EmitReturnT::Out>,
+    {
+        Expr::raw(Term(
+            term::FOLD,
+            (self.ast, base.into_ast(), combine.into_function_expr().ast),
+            FoldOptions {
+                emit: emit.into_function_expr(),
+                final_emit: (),
+                _phantom: PhantomData,
+            },
+        ))
+    }
+
+    /// Sets the `final_emit` option for `fold_emit`: a function called with the final
+    /// accumulator, whose returned array is appended to the result.
+    pub fn with_final_emit<FunctionT>(self, final_emit: FunctionT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<FinalEmitOption, FunctionT>,
+    {
+        Expr::raw(self.ast.with_option(final_emit))
+    }
+
+    /// Count the number of elements in the sequence.
+    pub fn count(self) -> Expr<OutT::Aggregated, Term<(AstT,)>>
+    where
+        OutT: Aggregate<NumberOut>,
+    {
+        Expr::raw(term(term::COUNT, (self.ast,)))
+    }
+
+    pub fn sum(self) -> Expr<OutT::Aggregated, Term<(AstT,)>>
+    where
+        OutT: Aggregate<NumberOut>,
+        OutT::SequenceItem: IsNumber,
+    {
+        Expr::raw(term(term::SUM, (self.ast,)))
+    }
+
+    pub fn avg(self) -> Expr<OutT::Aggregated, Term<(AstT,)>>
+    where
+        OutT: Aggregate<NumberOut>,
+        OutT::SequenceItem: IsNumber,
+    {
+        Expr::raw(term(term::AVG, (self.ast,)))
+    }
+
+    pub fn min(self) -> Expr<OutT::Aggregated, Term<(AstT,)>>
+    where
+        OutT: Aggregate<<OutT as IsSequence>::SequenceItem>,
+    {
+        Expr::raw(term(term::MIN, (self.ast,)))
+    }
+
+    pub fn max(self) -> Expr<OutT::Aggregated, Term<(AstT,)>>
+    where
+        OutT: Aggregate<<OutT as IsSequence>::SequenceItem>,
+    {
+        Expr::raw(term(term::MAX, (self.ast,)))
+    }
+
+    // FIXME: Implement distinct.
+    pub fn distinct(self) -> Expr<OutT, Term<(AstT,)>>
+    where
+        OutT: IsSequence,
+    {
+        Expr::raw(term(term::DISTINCT, (self.ast,)))
+    }
+
+    // FIXME: Implement contains.
+    pub fn contains<ValueT>(self, value: ValueT) -> Expr<BoolOut, Term<(AstT, ValueT::Ast)>>
+    where
+        OutT: IsSequence,
+        OutT::SequenceItem: IsEqualComparable<ValueT::Out>,
+        ValueT: IntoExpr,
+    {
+        Expr::raw(term(term::CONTAINS, (self.ast, value.into_ast())))
+    }
+
+    // FIXME: Implement r.row implicit var functions.
+
+    /// Plucks out one or more attributes from either an object or a sequence of objects
+    /// (projection). Selectors are field names, arrays of selectors, or objects for nested fields,
+    /// e.g. `r::object((("address", ["city", "zip"]),))`.
+    pub fn pluck<SelectorT, ArgsAstT, SelectorsT>(
+        self,
+        selectors: SelectorsT,
+    ) -> Expr<OutT::ObjectOrObjectSequence, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsObjectOrObjectSequence,
+        SelectorT: IsSelector,
+        SelectorsT: Into<Args<SelectorT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::PLUCK, (self.ast, selectors.into().ast)))
+    }
+
+    /// The opposite of `pluck`; takes an object or a sequence of objects, and returns them with
+    /// the specified paths removed.
+    pub fn without<SelectorT, ArgsAstT, SelectorsT>(
+        self,
+        selectors: SelectorsT,
+    ) -> Expr<OutT::ObjectOrObjectSequence, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsObjectOrObjectSequence,
+        SelectorT: IsSelector,
+        SelectorsT: Into<Args<SelectorT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::WITHOUT, (self.ast, selectors.into().ast)))
+    }
+
+    /// Merge an object into an object, or into each object of a sequence, preferring its fields
+    /// in case of conflict. Nested objects are merged recursively, unless wrapped in
+    /// `r::literal`.
+    pub fn merge<ObjectT>(
+        self,
+        object: ObjectT,
+    ) -> Expr<OutT::ObjectOrObjectSequence, Term<(AstT, ObjectT::Ast)>>
+    where
+        OutT: IsObjectOrObjectSequence,
+        ObjectT: IntoExpr,
+        ObjectT::Out: IsObject,
+    {
+        Expr::raw(term(term::MERGE, (self.ast, object.into_ast())))
+    }
+
+    /// Like `merge`, but with the object returned by a function of the object (or of each object
+    /// in the sequence).
+    pub fn merge_with<ReturnT, FunctionT>(
+        self,
+        with: FunctionT,
+    ) -> Expr<OutT::ObjectOrObjectSequence, Term<(AstT, FunctionT::FunctionAst)>>
+    where
+        OutT: IsObjectOrObjectSequence,
+        ReturnT: IntoExpr,
+        ReturnT::Out: IsObject,
+        FunctionT: FnOnce(Var<OutT::Object>) -> ReturnT
+            + IntoFunctionExpr<(OutT::Object,), ReturnT::Out>,
+    {
+        Expr::raw(term(term::MERGE, (self.ast, with.into_function_expr().ast)))
+    }
+
+    /// Append a value to an array.
+    pub fn append<ValueT>(
+        self,
+        value: ValueT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, ValueT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        ValueT: IntoExpr<Out = OutT::SequenceItem>,
+    {
+        Expr::raw(term(term::APPEND, (self.ast, value.into_ast())))
+    }
+
+    /// Prepend a value to an array.
+    pub fn prepend<ValueT>(
+        self,
+        value: ValueT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, ValueT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        ValueT: IntoExpr<Out = OutT::SequenceItem>,
+    {
+        Expr::raw(term(term::PREPEND, (self.ast, value.into_ast())))
+    }
+
+    /// Remove all the elements of `other` from an array.
+    pub fn difference<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        OtherT: IntoExpr<Out = ArrayOut<OutT::SequenceItem>>,
+    {
+        Expr::raw(term(term::DIFFERENCE, (self.ast, other.into_ast())))
+    }
+
+    /// Add a value to an array, treating it as a set (duplicates are removed).
+    pub fn set_insert<ValueT>(
+        self,
+        value: ValueT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, ValueT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        ValueT: IntoExpr<Out = OutT::SequenceItem>,
+    {
+        Expr::raw(term(term::SET_INSERT, (self.ast, value.into_ast())))
+    }
+
+    /// Add the elements of `other` to an array, treating both as sets (duplicates are
+    /// removed).
+    pub fn set_union<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        OtherT: IntoExpr<Out = ArrayOut<OutT::SequenceItem>>,
+    {
+        Expr::raw(term(term::SET_UNION, (self.ast, other.into_ast())))
+    }
+
+    /// Intersect two arrays, treating both as sets (duplicates are removed).
+    pub fn set_intersection<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        OtherT: IntoExpr<Out = ArrayOut<OutT::SequenceItem>>,
+    {
+        Expr::raw(term(term::SET_INTERSECTION, (self.ast, other.into_ast())))
+    }
+
+    /// Remove the elements of `other` from an array, treating both as sets (duplicates are
+    /// removed).
+    pub fn set_difference<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        OtherT: IntoExpr<Out = ArrayOut<OutT::SequenceItem>>,
+    {
+        Expr::raw(term(term::SET_DIFFERENCE, (self.ast, other.into_ast())))
+    }
+
+    /// Get a single field from an object. If called on a sequence, gets that field from every
+    /// object in the sequence, skipping objects that lack it.
+    pub fn get_field<KeyT: IntoExpr<Out=StringOut>>(
+        self,
+        key: KeyT,
+    ) -> Expr<OutT::AnyOrAnySequence, Term<(AstT, KeyT::Ast)>>
+    where
+        OutT: IsObjectOrObjectSequence,
+    {
+        Expr::raw(term(term::GET_FIELD, (self.ast, key.into_ast())))
+    }
+
+    /// Alias for `get_field`. `std::ops::Index` can't stand in for it, since it has to return a
+    /// reference rather than a new expression; chain it instead, so `doc["a"]["b"]` is written
+    /// `doc.g("a").g("b")`.
+    pub fn g<KeyT: IntoExpr<Out=StringOut>>(
+        self,
+        key: KeyT,
+    ) -> Expr<OutT::AnyOrAnySequence, Term<(AstT, KeyT::Ast)>>
+    where
+        OutT: IsObjectOrObjectSequence,
+    {
+        Expr::raw(term(term::GET_FIELD, (self.ast, key.into_ast())))
+    }
+
+    /// Alias for `get_field` that asserts the result is a object.
+    pub fn get_object<KeyT: IntoExpr<Out=StringOut>>(self, key: KeyT) -> Expr<ObjectOut, Term<(AstT, KeyT::Ast)>>
+    where
+        OutT: IsObjectOrObjectSequence,
+    {
+        self.get_field(key).as_object()
+    }
+
+    /// Test whether an object has all of the specified fields; on a sequence, keep only the
+    /// objects which do. Takes the same selectors as `pluck`.
+    pub fn has_fields<SelectorT, ArgsAstT, SelectorsT>(
+        self,
+        selectors: SelectorsT,
+    ) -> Expr<OutT::BoolOrSequence, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsObjectOrObjectSequence,
+        SelectorT: IsSelector,
+        SelectorsT: Into<Args<SelectorT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::HAS_FIELDS, (self.ast, selectors.into().ast)))
+    }
+
+    /// Insert a value in to an array at a given index.
+    pub fn insert_at<IndexT, ValueT>(
+        self,
+        index: IndexT,
+        value: ValueT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, IndexT::Ast, ValueT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        IndexT: IntoExpr<Out = NumberOut>,
+        ValueT: IntoExpr<Out = OutT::SequenceItem>,
+    {
+        Expr::raw(term(
+            term::INSERT_AT,
+            (self.ast, index.into_ast(), value.into_ast()),
+        ))
+    }
+
+    /// Insert the elements of another array in to an array at a given index.
+    pub fn splice_at<IndexT, OtherT>(
+        self,
+        index: IndexT,
+        other: OtherT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, IndexT::Ast, OtherT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        IndexT: IntoExpr<Out = NumberOut>,
+        OtherT: IntoExpr<Out = ArrayOut<OutT::SequenceItem>>,
+    {
+        Expr::raw(term(
+            term::SPLICE_AT,
+            (self.ast, index.into_ast(), other.into_ast()),
+        ))
+    }
+
+    /// Remove the element at a given index from an array. Negative indices count from the end.
+    pub fn delete_at<IndexT>(self, index: IndexT) -> Expr<OutT::Rebound, Term<(AstT, IndexT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        IndexT: IntoExpr<Out = NumberOut>,
+    {
+        Expr::raw(term(term::DELETE_AT, (self.ast, index.into_ast())))
+    }
+
+    /// Remove the elements between `start` (inclusive) and `end` (exclusive) from an array.
+    pub fn delete_range<StartT, EndT>(
+        self,
+        start: StartT,
+        end: EndT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, StartT::Ast, EndT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        StartT: IntoExpr<Out = NumberOut>,
+        EndT: IntoExpr<Out = NumberOut>,
+    {
+        Expr::raw(term(
+            term::DELETE_AT,
+            (self.ast, start.into_ast(), end.into_ast()),
+        ))
+    }
+
+    /// Change a value in an array at a given index.
+    pub fn change_at<IndexT, ValueT>(
+        self,
+        index: IndexT,
+        value: ValueT,
+    ) -> Expr<OutT::Rebound, Term<(AstT, IndexT::Ast, ValueT::Ast)>>
+    where
+        OutT: IsArray + Rebind<<OutT as IsSequence>::SequenceItem>,
+        IndexT: IntoExpr<Out = NumberOut>,
+        ValueT: IntoExpr<Out = OutT::SequenceItem>,
+    {
+        Expr::raw(term(
+            term::CHANGE_AT,
+            (self.ast, index.into_ast(), value.into_ast()),
+        ))
+    }
+
+    /// Return an array containing all of an object's keys.
+    pub fn keys(self) -> Expr<ArrayOut<StringOut>, Term<(AstT,)>>
+    where
+        OutT: IsObject,
+    {
+        Expr::raw(term(term::KEYS, (self.ast,)))
+    }
+
+    /// Return an array containing all of an object's values, in the same order as `keys`.
+    pub fn values(self) -> Expr<ArrayOut<AnyOut>, Term<(AstT,)>>
+    where
+        OutT: IsObject,
+    {
+        Expr::raw(term(term::VALUES, (self.ast,)))
+    }
+
+    /// Match a string against a regular expression (RE2 syntax). Returns null if there is no
+    /// match, otherwise an object which deserializes into a `types::RegexMatch`.
+    ///
+    /// Named `match_` since `match` is a keyword. To filter on a regex, compare with `Null`:
+    /// `doc.get_field("name").as_string().match_("^A").ne(Null)`.
+    pub fn match_<RegexT>(self, regex: RegexT) -> Expr<NullOr<MatchOut>, Term<(AstT, RegexT::Ast)>>
+    where
+        OutT: IsString,
+        RegexT: IntoExpr,
+        RegexT::Out: IsString,
+    {
+        Expr::raw(term(term::MATCH, (self.ast, regex.into_ast())))
+    }
+
+    /// Split a string on whitespace, discarding empty strings.
+    pub fn split(self) -> Expr<ArrayOut<StringOut>, Term<(AstT,)>>
+    where
+        OutT: IsString,
+    {
+        Expr::raw(term(term::SPLIT, (self.ast,)))
+    }
+
+    /// Split a string on whitespace at most `max_splits` times, discarding empty strings.
+    pub fn split_max<MaxT>(
+        self,
+        max_splits: MaxT,
+    ) -> Expr<ArrayOut<StringOut>, Term<(AstT, Null, MaxT::Ast)>>
+    where
+        OutT: IsString,
+        MaxT: IntoExpr<Out=NumberOut>,
+    {
+        Expr::raw(term(term::SPLIT, (self.ast, Null, max_splits.into_ast())))
+    }
+
+    /// Split a string on a separator. An empty separator splits the string into characters.
+    pub fn split_on<SeparatorT>(
+        self,
+        separator: SeparatorT,
+    ) -> Expr<ArrayOut<StringOut>, Term<(AstT, SeparatorT::Ast)>>
+    where
+        OutT: IsString,
+        SeparatorT: IntoExpr,
+        SeparatorT::Out: IsString,
+    {
+        Expr::raw(term(term::SPLIT, (self.ast, separator.into_ast())))
+    }
+
+    /// Split a string on a separator at most `max_splits` times.
+    pub fn split_on_max<SeparatorT, MaxT>(
+        self,
+        separator: SeparatorT,
+        max_splits: MaxT,
+    ) -> Expr<ArrayOut<StringOut>, Term<(AstT, SeparatorT::Ast, MaxT::Ast)>>
+    where
+        OutT: IsString,
+        SeparatorT: IntoExpr,
+        SeparatorT::Out: IsString,
+        MaxT: IntoExpr<Out=NumberOut>,
+    {
+        Expr::raw(term(
+            term::SPLIT,
+            (self.ast, separator.into_ast(), max_splits.into_ast()),
+        ))
+    }
+
+    /// Uppercase a string.
+    pub fn upcase(self) -> Expr<StringOut, Term<(AstT,)>>
+    where
+        OutT: IsString,
+    {
+        Expr::raw(term(term::UPCASE, (self.ast,)))
+    }
+
+    /// Lowercase a string.
+    pub fn downcase(self) -> Expr<StringOut, Term<(AstT,)>>
+    where
+        OutT: IsString,
+    {
+        Expr::raw(term(term::DOWNCASE, (self.ast,)))
+    }
+
+    /// Sum two or more numbers, or concatenate two or more strings or arrays. Pass
+    /// `r::args`/`r::args_of` for more than one operand.
+    pub fn add<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<OutT::Output, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: CanAdd<OtherT>,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::ADD, (self.ast, others.into().ast)))
+    }
+
+    /// Subtract a number from a number, or from a time (as seconds). Subtracting two times gives
+    /// the number of seconds between them.
+    pub fn sub<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<OutT::Output, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: CanSub<OtherT::Out>,
+        OtherT: IntoExpr,
+    {
+        Expr::raw(term(term::SUB, (self.ast, other.into_ast())))
+    }
+
+    /// Multiply two numbers.
+    pub fn mul<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<NumberOut, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: IsNumber,
+        OtherT::Out: IsNumber,
+        OtherT: IntoExpr,
+    {
+        Expr::raw(term(term::MUL, (self.ast, other.into_ast())))
+    }
+
+    /// Divide two numbers. The result is a float, even for integer operands; use `floor` for
+    /// integer division.
+    pub fn div<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<NumberOut, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: IsNumber,
+        OtherT::Out: IsNumber,
+        OtherT: IntoExpr,
+    {
+        Expr::raw(term(term::DIV, (self.ast, other.into_ast())))
+    }
+
+    /// The remainder of dividing two integers, which has the sign of the dividend. Named `modulo`
+    /// since `mod` is a keyword.
+    pub fn modulo<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<NumberOut, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: IsNumber,
+        OtherT::Out: IsNumber,
+        OtherT: IntoExpr,
+    {
+        Expr::raw(term(term::MOD, (self.ast, other.into_ast())))
+    }
+
+    /// Compute the logical "and" of two or more values. Pass `r::args`/`r::args_of` for more
+    /// than one operand.
+    pub fn and<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<BoolOut, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsBool,
+        OtherT: IsBool,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::AND, (self.ast, others.into().ast)))
+    }
+
+    /// Compute the logical "or" of two or more values. Pass `r::args`/`r::args_of` for more
+    /// than one operand.
+    pub fn or<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<BoolOut, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsBool,
+        OtherT: IsBool,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::OR, (self.ast, others.into().ast)))
+    }
+
+    /// Test if two or more values are equal. Pass `r::args`/`r::args_of` for more than one
+    /// operand.
+    pub fn eq<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<BoolOut, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsEqualComparable<OtherT>,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::EQ, (self.ast, others.into().ast)))
+    }
+
+    /// Test if two or more values are not equal. Pass `r::args`/`r::args_of` for more than
+    /// one operand.
+    pub fn ne<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<BoolOut, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsEqualComparable<OtherT>,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::NE, (self.ast, others.into().ast)))
+    }
+
+    /// Test if the first value is greater than the following ones (each one greater than the
+    /// next when more operands are passed with `r::args`/`r::args_of`).
+    pub fn gt<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<BoolOut, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsOrderComparable<OtherT>,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::GT, (self.ast, others.into().ast)))
+    }
+
+    /// Test if the first value is greater than or equal to the following ones.
+    pub fn ge<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<BoolOut, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsOrderComparable<OtherT>,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::GE, (self.ast, others.into().ast)))
+    }
+
+    /// Test if the first value is less than the following ones (each one less than the next
+    /// when more operands are passed with `r::args`/`r::args_of`).
+    pub fn lt<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<BoolOut, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsOrderComparable<OtherT>,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::LT, (self.ast, others.into().ast)))
+    }
+
+    /// Test if the first value is less than or equal to the following ones.
+    pub fn le<OtherT, ArgsAstT, OthersT>(
+        self,
+        others: OthersT,
+    ) -> Expr<BoolOut, Term<(AstT, ArgsAstT)>>
+    where
+        OutT: IsOrderComparable<OtherT>,
+        OthersT: Into<Args<OtherT, ArgsAstT>>,
+    {
+        Expr::raw(term(term::LE, (self.ast, others.into().ast)))
+    }
+
+    /// Compute the logical inverse (not) of a boolean.
+    pub fn not(self) -> Expr<BoolOut, Term<(AstT,)>>
+    where
+        OutT: IsBool,
+    {
+        Expr::raw(term(term::NOT, (self.ast,)))
+    }
+
+    /// Sets the `float` option for `r::random_to` and `r::random_between`, expects a bool:
+    /// whether to generate a float rather than an integer.
+    pub fn with_float<FloatT>(self, float: FloatT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<FloatOption, FloatT>,
+    {
+        Expr::raw(self.ast.with_option(float))
+    }
+
+    /// Round a number to the nearest integer, rounding halves away from zero.
+    pub fn round(self) -> Expr<NumberOut, Term<(AstT,)>>
+    where
+        OutT: IsNumber,
+    {
+        Expr::raw(term(term::ROUND, (self.ast,)))
+    }
+
+    /// Round a number up to the nearest integer.
+    pub fn ceil(self) -> Expr<NumberOut, Term<(AstT,)>>
+    where
+        OutT: IsNumber,
+    {
+        Expr::raw(term(term::CEIL, (self.ast,)))
+    }
+
+    /// Round a number down to the nearest integer.
+    pub fn floor(self) -> Expr<NumberOut, Term<(AstT,)>>
+    where
+        OutT: IsNumber,
+    {
+        Expr::raw(term(term::FLOOR, (self.ast,)))
+    }
+
+    /// Get all documents whose geospatial index value intersects the given geometry. The `index`
+    /// option is required and must name a geo index.
+    pub fn get_intersecting<GeometryT>(
+        self,
+        geometry: GeometryT,
+    ) -> Expr<SelectionOut<ObjectOut>, Term<(AstT, GeometryT::Ast), GetAllOptions>>
+    where
+        OutT: IsTable,
+        GeometryT: IntoExpr,
+        GeometryT::Out: IsGeometry,
+    {
+        Expr::raw(term(
+            term::GET_INTERSECTING,
+            (self.ast, geometry.into_ast()),
+        ))
+    }
+
+    /// Get the documents whose geospatial index value is closest to a point, as an array of
+    /// `{"dist": ..., "doc": ...}` objects (see `types::Nearest`) sorted by distance. The `index`
+    /// option is required and must name a geo index. Also accepts the `max_results`, `max_dist`,
+    /// `unit` and `geo_system` options.
+    pub fn get_nearest<PointT>(
+        self,
+        point: PointT,
+    ) -> Expr<ArrayOut<ObjectOut>, Term<(AstT, PointT::Ast), GetNearestOptions>>
+    where
+        OutT: IsTable,
+        PointT: IntoExpr,
+        PointT::Out: IsGeometry,
+    {
+        Expr::raw(term(term::GET_NEAREST, (self.ast, point.into_ast())))
+    }
+
+    /// The distance between two geometries, at least one of which must be a point. Accepts the
+    /// `geo_system` and `unit` options.
+    pub fn distance<OtherT>(
+        self,
+        other: OtherT,
+    ) -> Expr<NumberOut, Term<(AstT, OtherT::Ast), DistanceOptions>>
+    where
+        OutT: IsGeometry,
+        OtherT: IntoExpr,
+        OtherT::Out: IsGeometry,
+    {
+        Expr::raw(term(term::DISTANCE, (self.ast, other.into_ast())))
+    }
+
+    /// Whether two geometries intersect; on a sequence of geometries, the ones which intersect
+    /// the given geometry.
+    pub fn intersects<GeometryT>(
+        self,
+        geometry: GeometryT,
+    ) -> Expr<OutT::BoolOrSequence, Term<(AstT, GeometryT::Ast)>>
+    where
+        OutT: IsGeometryOrGeometrySequence,
+        GeometryT: IntoExpr,
+        GeometryT::Out: IsGeometry,
+    {
+        Expr::raw(term(term::INTERSECTS, (self.ast, geometry.into_ast())))
+    }
+
+    /// Whether a polygon completely contains a geometry; on a sequence of polygons, the ones which
+    /// contain the given geometry.
+    pub fn includes<GeometryT>(
+        self,
+        geometry: GeometryT,
+    ) -> Expr<OutT::BoolOrSequence, Term<(AstT, GeometryT::Ast)>>
+    where
+        OutT: IsGeometryOrGeometrySequence,
+        GeometryT: IntoExpr,
+        GeometryT::Out: IsGeometry,
+    {
+        Expr::raw(term(term::INCLUDES, (self.ast, geometry.into_ast())))
+    }
+
+    /// Convert a line into a polygon, closing it if its endpoints differ.
+    pub fn fill(self) -> Expr<GeometryOut, Term<(AstT,)>>
+    where
+        OutT: IsGeometry,
+    {
+        Expr::raw(term(term::FILL, (self.ast,)))
+    }
+
+    /// Subtract a polygon, which must be completely contained in this one, from this polygon,
+    /// making a hole in it.
+    pub fn polygon_sub<OtherT>(self, other: OtherT) -> Expr<GeometryOut, Term<(AstT, OtherT::Ast)>>
+    where
+        OutT: IsGeometry,
+        OtherT: IntoExpr,
+        OtherT::Out: IsGeometry,
+    {
+        Expr::raw(term(term::POLYGON_SUB, (self.ast, other.into_ast())))
+    }
+
+    /// Convert a geometry to a GeoJSON object.
+    pub fn to_geojson(self) -> Expr<ObjectOut, Term<(AstT,)>>
+    where
+        OutT: IsGeometry,
+    {
+        Expr::raw(term(term::TO_GEOJSON, (self.ast,)))
+    }
+
+    /// Sets the `unit` option for `distance`, `circle` and `get_nearest`, expects a string: one of
+    /// `"m"` (the default), `"km"`, `"mi"`, `"nm"` or `"ft"`.
+    pub fn with_unit<UnitT>(self, unit: UnitT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<UnitOption, UnitT>,
+    {
+        Expr::raw(self.ast.with_option(unit))
+    }
+
+    /// Sets the `geo_system` option for `distance`, `circle` and `get_nearest`, expects a string:
+    /// `"WGS84"` (the default) or `"unit_sphere"`.
+    pub fn with_geo_system<SystemT>(self, geo_system: SystemT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<GeoSystemOption, SystemT>,
+    {
+        Expr::raw(self.ast.with_option(geo_system))
+    }
+
+    /// Sets the `max_dist` option for `get_nearest`, expects a number: the maximum distance from
+    /// the point, in `unit`s (100km by default).
+    pub fn with_max_dist<DistanceT>(self, max_dist: DistanceT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<MaxDistOption, DistanceT>,
+    {
+        Expr::raw(self.ast.with_option(max_dist))
+    }
+
+    /// Sets the `max_results` option for `get_nearest`, expects a number (100 by default).
+    pub fn with_max_results<ResultsT>(self, max_results: ResultsT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<MaxResultsOption, ResultsT>,
     {
-        Expr::raw(term(
-            term::CONCAT_MAP,
-            (self.ast, concat_map.into_function_expr().ast),
-        ))
+        Expr::raw(self.ast.with_option(max_results))
     }
 
-    // FIXME: implement order_by
+    /// Sets the `num_vertices` option for `circle`, expects a number: how many vertices to
+    /// approximate the circle with (32 by default).
+    pub fn with_num_vertices<VerticesT>(self, vertices: VerticesT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<NumVerticesOption, VerticesT>,
+    {
+        Expr::raw(self.ast.with_option(vertices))
+    }
 
-    /// Skip a number of elements from the head of the sequence.
-    pub fn skip<NumT>(self, n: NumT) -> Expr<OutT, Term<(AstT, NumT::Ast)>>
+    /// Sets the `fill` option for `circle`, expects a bool: whether to return a polygon (the
+    /// default) rather than a line.
+    pub fn with_fill<FillT>(self, fill: FillT) -> Expr<OutT, AstT::WithOption>
     where
-        NumT: IntoExpr<Out=NumberOut>,
-        OutT: IsSequence,
+        AstT: WithOption<FillOption, FillT>,
     {
-        Expr::raw(term(term::SKIP, (self.ast, n.into_ast())))
+        Expr::raw(self.ast.with_option(fill))
     }
 
-    /// End the sequence after the given number of elements.
-    pub fn limit<NumT>(self, n: NumT) -> Expr<OutT, Term<(AstT, NumT::Ast)>>
+    /// Return a new time with a different timezone, given as `"[+-]HH:MM"` or `"Z"`. The moment
+    /// in time represented is unchanged.
+    pub fn in_timezone<TimezoneT>(
+        self,
+        timezone: TimezoneT,
+    ) -> Expr<TimeOut, Term<(AstT, TimezoneT::Ast)>>
     where
-        NumT: IntoExpr<Out=NumberOut>,
-        OutT: IsSequence,
+        OutT: IsTime,
+        TimezoneT: IntoExpr,
+        TimezoneT::Out: IsString,
     {
-        Expr::raw(term(term::LIMIT, (self.ast, n.into_ast())))
+        Expr::raw(term(term::IN_TIMEZONE, (self.ast, timezone.into_ast())))
     }
 
-    /// Return the elements of a sequence within the specified range.
-    pub fn slice_after<NumT>(self, start: NumT) -> Expr<OutT, Term<(AstT, NumT::Ast)>>
+    /// The timezone of a time, as `"[+-]HH:MM"`.
+    pub fn timezone(self) -> Expr<StringOut, Term<(AstT,)>>
     where
-        NumT: IntoExpr<Out=NumberOut>,
-        OutT: IsSequence,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::SLICE, (self.ast, start.into_ast())))
+        Expr::raw(term(term::TIMEZONE, (self.ast,)))
     }
 
-    /// Return the elements of a sequence within the specified range.
-    /// FIXME: Add left_bound and right_bound options.
-    pub fn slice<StartT, EndT>(
+    /// Whether a time is between two other times. Accepts the `left_bound` and `right_bound`
+    /// options, like `between`: by default the start is included and the end is not.
+    pub fn during<StartT, EndT>(
         self,
         start: StartT,
         end: EndT,
-    ) -> Expr<OutT, Term<(AstT, StartT::Ast, EndT::Ast)>>
+    ) -> Expr<BoolOut, Term<(AstT, StartT::Ast, EndT::Ast), DuringOptions>>
     where
-        StartT: IntoExpr<Out=NumberOut>,
-        EndT: IntoExpr<Out=NumberOut>,
-        OutT: IsSequence,
+        OutT: IsTime,
+        StartT: MinLimitFor<TimeOut>,
+        EndT: MaxLimitFor<TimeOut>,
     {
         Expr::raw(term(
-            term::SLICE,
+            term::DURING,
             (self.ast, start.into_ast(), end.into_ast()),
         ))
     }
 
-    /// Get the nth element of a sequence, counting from zero. If the argument is negative, count
-    /// from the last element.
-    pub fn nth<NumT>(self, n: NumT) -> Expr<OutT::Select, Term<(AstT, NumT::Ast)>>
+    /// A new time which is the start of the day of a time, in the same timezone.
+    pub fn date(self) -> Expr<TimeOut, Term<(AstT,)>>
     where
-        NumT: IntoExpr<Out=NumberOut>,
-        OutT: IsSequence,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::NTH, (self.ast, n.into_ast())))
+        Expr::raw(term(term::DATE, (self.ast,)))
     }
 
-    // FIXME: Implement offsets_of.
-
-    // FIXME: Implement is_empty.
-    pub fn is_empty(self) -> Expr<BoolOut, Term<(AstT,)>>
+    /// The number of seconds elapsed since the start of the day of a time.
+    pub fn time_of_day(self) -> Expr<NumberOut, Term<(AstT,)>>
     where
-        OutT: IsSequence,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::IS_EMPTY, (self.ast,)))
+        Expr::raw(term(term::TIME_OF_DAY, (self.ast,)))
     }
 
-    // FIXME: Implement union.
-    // FIXME: Implement sample.
+    /// The year of a time.
+    pub fn year(self) -> Expr<NumberOut, Term<(AstT,)>>
+    where
+        OutT: IsTime,
+    {
+        Expr::raw(term(term::YEAR, (self.ast,)))
+    }
 
-    // FIXME: Implement group.
-    // FIXME: Implement ungroup.
-    // FIXME: Implement reduce.
-    // FIXME: Implement fold.
+    /// The month of a time, from 1 to 12 (comparable with `r::january()` etc.).
+    pub fn month(self) -> Expr<NumberOut, Term<(AstT,)>>
+    where
+        OutT: IsTime,
+    {
+        Expr::raw(term(term::MONTH, (self.ast,)))
+    }
 
-    // FIXME: Implement sum.
-    pub fn sum(self) -> Expr<NumberOut, Term<(AstT,)>>
+    /// The day of the month of a time, from 1 to 31.
+    pub fn day(self) -> Expr<NumberOut, Term<(AstT,)>>
     where
-        OutT: IsSequence,
-        OutT::SequenceItem: IsNumber,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::SUM, (self.ast,)))
+        Expr::raw(term(term::DAY, (self.ast,)))
     }
 
-    // FIXME: Implement avg.
-    pub fn avg(self) -> Expr<NumberOut, Term<(AstT,)>>
+    /// The day of the week of a time, from 1 (Monday) to 7 (comparable with `r::monday()` etc.).
+    pub fn day_of_week(self) -> Expr<NumberOut, Term<(AstT,)>>
     where
-        OutT: IsSequence,
-        OutT::SequenceItem: IsNumber,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::AVG, (self.ast,)))
+        Expr::raw(term(term::DAY_OF_WEEK, (self.ast,)))
     }
 
-    // FIXME: Implement min.
-    pub fn min(self) -> Expr<OutT::SequenceItem, Term<(AstT,)>>
+    /// The day of the year of a time, from 1 to 366.
+    pub fn day_of_year(self) -> Expr<NumberOut, Term<(AstT,)>>
     where
-        OutT: IsSequence,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::MIN, (self.ast,)))
+        Expr::raw(term(term::DAY_OF_YEAR, (self.ast,)))
     }
 
-    // FIXME: Implement max.
-    pub fn max(self) -> Expr<OutT::SequenceItem, Term<(AstT,)>>
+    /// The hour of a time, from 0 to 23.
+    pub fn hours(self) -> Expr<NumberOut, Term<(AstT,)>>
     where
-        OutT: IsSequence,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::MAX, (self.ast,)))
+        Expr::raw(term(term::HOURS, (self.ast,)))
     }
 
-    // FIXME: Implement distinct.
-    pub fn distinct(self) -> Expr<OutT, Term<(AstT,)>>
+    /// The minute of a time, from 0 to 59.
+    pub fn minutes(self) -> Expr<NumberOut, Term<(AstT,)>>
     where
-        OutT: IsSequence,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::DISTINCT, (self.ast,)))
+        Expr::raw(term(term::MINUTES, (self.ast,)))
     }
 
-    // FIXME: Implement contains.
-    pub fn contains<ValueT>(self, value: ValueT) -> Expr<BoolOut, Term<(AstT, ValueT::Ast)>>
+    /// The seconds of a time, from 0 to just under 60, with fractional seconds.
+    pub fn seconds(self) -> Expr<NumberOut, Term<(AstT,)>>
     where
-        OutT: IsSequence,
-        OutT::SequenceItem: IsEqualComparable<ValueT::Out>,
-        ValueT: IntoExpr,
+        OutT: IsTime,
     {
-        Expr::raw(term(term::CONTAINS, (self.ast, value.into_ast())))
+        Expr::raw(term(term::SECONDS, (self.ast,)))
     }
 
-    // FIXME: Implement r.row implicit var functions.
+    /// Convert a time to a string in ISO 8601 format.
+    pub fn to_iso8601(self) -> Expr<StringOut, Term<(AstT,)>>
+    where
+        OutT: IsTime,
+    {
+        Expr::raw(term(term::TO_ISO8601, (self.ast,)))
+    }
 
-    // FIXME: Implement pluck.
-    // FIXME: Implement without.
-    // FIXME: Implement merge.
-    // FIXME: Implement append.
-    // FIXME: Implement prepend.
-    // FIXME: Implement difference.
-    // FIXME: Implement setInsert.
-    // FIXME: Implement setUnion.
-    // FIXME: Implement setIntersection.
-    // FIXME: Implement setDifference.
+    /// Convert a time to the number of seconds since the UNIX epoch, with fractional seconds.
+    pub fn to_epoch_time(self) -> Expr<NumberOut, Term<(AstT,)>>
+    where
+        OutT: IsTime,
+    {
+        Expr::raw(term(term::TO_EPOCH_TIME, (self.ast,)))
+    }
 
-    /// Get a single field from an object. If called on a sequence, gets that field from every
-    /// object in the sequence, skipping objects that lack it.
-    pub fn get_field<KeyT: IntoExpr<Out=StringOut>>(
+    /// Call a function on this value, e.g. to bind the result of a query to a variable in order
+    /// to use it several times. Named `do_` since `do` is a keyword.
+    pub fn do_<ReturnT, FunctionT>(
         self,
-        key: KeyT,
-    ) -> Expr<OutT::AnyOrAnySequence, Term<(AstT, KeyT::Ast)>>
+        function: FunctionT,
+    ) -> Expr<ReturnT::Out, Term<(FunctionT::FunctionAst, AstT)>>
     where
-        OutT: IsObjectOrObjectSequence,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<OutT>) -> ReturnT + IntoFunctionExpr<(OutT,), ReturnT::Out>,
     {
-        Expr::raw(term(term::GET_FIELD, (self.ast, key.into_ast())))
+        Expr::raw(term(
+            term::FUNCALL,
+            (function.into_function_expr().ast, self.ast),
+        ))
     }
 
-    /// Alias for `get_field`.
-    pub fn g<KeyT: IntoExpr<Out=StringOut>>(
+    /// Call a write query (or an array of write queries) for each element of a sequence, and
+    /// return the combined write result. Tuples of different write queries are `ArrayOut<AnyOut>`,
+    /// convert them with `r::expr((insert, delete)).items_as::<WriteOut>()`.
+    pub fn for_each<ReturnT, FunctionT>(
         self,
-        key: KeyT,
-    ) -> Expr<OutT::AnyOrAnySequence, Term<(AstT, KeyT::Ast)>>
+        function: FunctionT,
+    ) -> Expr<WriteOut, Term<(AstT, FunctionT::FunctionAst)>>
     where
-        OutT: IsObjectOrObjectSequence,
+        OutT: IsSequence,
+        ReturnT: IntoExpr,
+        ReturnT::Out: IsWrite,
+        FunctionT: FnOnce(Var<OutT::SequenceItem>) -> ReturnT
+            + IntoFunctionExpr<(OutT::SequenceItem,), ReturnT::Out>,
     {
-        Expr::raw(term(term::GET_FIELD, (self.ast, key.into_ast())))
+        Expr::raw(term(
+            term::FOR_EACH,
+            (self.ast, function.into_function_expr().ast),
+        ))
     }
 
-    /// Alias for `get_field` that asserts the result is a object.
-    pub fn get_object<KeyT: IntoExpr<Out=StringOut>>(self, key: KeyT) -> Expr<ObjectOut, Term<(AstT, KeyT::Ast)>>
+    /// Replace a null value, or the error raised by a missing field or index, by `value`. Works on
+    /// `NullOr` values, whose fallback is either a value or a null of the same type, and on
+    /// untyped (`AnyOut`) values such as fields of a document, which accept any fallback.
+    pub fn default<ValueT>(self, value: ValueT) -> Expr<OutT::Output, Term<(AstT, ValueT::Ast)>>
     where
-        OutT: IsObjectOrObjectSequence,
+        OutT: CanDefault<ValueT::Out>,
+        ValueT: IntoExpr,
     {
-        self.get_field(key).as_object()
+        Expr::raw(term(term::DEFAULT, (self.ast, value.into_ast())))
+    }
+
+    /// Like `default`, but with the value returned by a function of the error message (or of
+    /// null, if the value was null rather than an error).
+    pub fn default_with<ReturnT, FunctionT>(
+        self,
+        function: FunctionT,
+    ) -> Expr<OutT::Output, Term<(AstT, FunctionT::FunctionAst)>>
+    where
+        OutT: CanDefault<ReturnT::Out>,
+        ReturnT: IntoExpr,
+        FunctionT: FnOnce(Var<NullOr<StringOut>>) -> ReturnT
+            + IntoFunctionExpr<(NullOr<StringOut>,), ReturnT::Out>,
+    {
+        Expr::raw(term(
+            term::DEFAULT,
+            (self.ast, function.into_function_expr().ast),
+        ))
     }
 
-    // FIXME: Implement hasFields
-    // FIXME: Implement insertAt
-    // FIXME: Implement spliceAt
-    // FIXME: Implement deleteAt
-    // FIXME: Implement changeAt
-    // FIXME: Implement keys
-    // FIXME: Implement values
-    // FIXME: Implement literal
-    // FIXME: Implement object
+    // FIXME: Implement js
+    // FIXME: Implement coerce_to (potentially as multiple functions)
+    // FIXME: Implement type_of
+    // FIXME: Implement json
+    // FIXME: Implement to_json
+
+    // FIXME: Implement http
+    // FIXME: Implement uuid
 
-    // FIXME: Implement match
-    // FIXME: Implement split
-    // FIXME: Implement upcase
-    // FIXME: Implement downcase
+    // FIXME: Implement grant
 
-    /// Sum two or more numbers, or concatenate two or more strings or arrays.
-    /// FIXME: Support more args.
-    pub fn add<OtherT>(
-        self,
-        other: OtherT,
-    ) -> Expr<OutT::Output, Term<(AstT, OtherT::Ast)>>
+    /// Query (read and/or update) the configuration of a database or table, which deserializes
+    /// into a `types::DbConfig` or `types::TableConfig` respectively.
+    pub fn config(self) -> Expr<SingleSelectionOut<OutT::Config>, Term<(AstT,)>>
     where
-        OutT: CanAdd<OtherT::Out>,
-        OtherT: IntoExpr,
+        OutT: IsDbOrTable,
     {
-        Expr::raw(term(term::ADD, (self.ast, other.into_ast())))
+        Expr::raw(term(term::CONFIG, (self.ast,)))
     }
 
-    // FIXME: Implement sub
-    pub fn sub<OtherT>(
-        self,
-        other: OtherT,
-    ) -> Expr<NumberOut, Term<(AstT, OtherT::Ast)>>
+    /// Return the status of a table, which deserializes into a `types::TableStatus`.
+    pub fn status(self) -> Expr<TableStatusOut, Term<(AstT,)>>
     where
-        OutT: IsNumber,
-        OtherT::Out: IsNumber,
-        OtherT: IntoExpr,
+        OutT: IsTable,
     {
-        Expr::raw(term(term::SUB, (self.ast, other.into_ast())))
+        Expr::raw(term(term::STATUS, (self.ast,)))
     }
 
-    // FIXME: Implement mul
-    pub fn mul<OtherT>(
-        self,
-        other: OtherT,
-    ) -> Expr<NumberOut, Term<(AstT, OtherT::Ast)>>
+    /// Wait for a table (or all the tables of a database) to be ready. Accepts the `wait_for`
+    /// and `timeout` options. The result deserializes into a `types::WaitResult`.
+    pub fn wait(self) -> Expr<WaitResultOut, Term<(AstT,), WaitOptions>>
     where
-        OutT: IsNumber,
-        OtherT::Out: IsNumber,
-        OtherT: IntoExpr,
+        OutT: IsDbOrTable,
     {
-        Expr::raw(term(term::MUL, (self.ast, other.into_ast())))
+        Expr::raw(term(term::WAIT, (self.ast,)))
     }
 
-    // FIXME: Implement div
-    pub fn div<OtherT>(
-        self,
-        other: OtherT,
-    ) -> Expr<NumberOut, Term<(AstT, OtherT::Ast)>>
+    /// Sets the `wait_for` option for `wait`, expects a string: one of
+    /// `"ready_for_outdated_reads"`, `"ready_for_reads"`, `"ready_for_writes"` (the default) or
+    /// `"all_replicas_ready"`.
+    pub fn with_wait_for<WaitForT>(self, wait_for: WaitForT) -> Expr<OutT, AstT::WithOption>
     where
-        OutT: IsNumber,
-        OtherT::Out: IsNumber,
-        OtherT: IntoExpr,
+        AstT: WithOption<WaitForOption, WaitForT>,
     {
-        Expr::raw(term(term::DIV, (self.ast, other.into_ast())))
+        Expr::raw(self.ast.with_option(wait_for))
     }
 
-    // FIXME: Implement mod
-    pub fn modulo<OtherT>(
-        self,
-        other: OtherT,
-    ) -> Expr<NumberOut, Term<(AstT, OtherT::Ast)>>
+    /// Sets the `timeout` option for `wait`, expects a number of seconds after which to give up
+    /// with an error.
+    pub fn with_timeout<TimeoutT>(self, timeout: TimeoutT) -> Expr<OutT, AstT::WithOption>
     where
-        OutT: IsNumber,
-        OtherT::Out: IsNumber,
-        OtherT: IntoExpr,
+        AstT: WithOption<TimeoutOption, TimeoutT>,
     {
-        Expr::raw(term(term::MOD, (self.ast, other.into_ast())))
+        Expr::raw(self.ast.with_option(timeout))
     }
 
-    // FIXME: Implement and
-    pub fn and<OtherT>(
+    /// Reconfigure the shards and replicas of a table (or of all the tables of a database).
+    /// `replicas` is either a number of replicas per shard, or an object mapping server tags to
+    /// numbers of replicas, in which case the `primary_replica_tag` option is required. Also
+    /// accepts the `nonvoting_replica_tags` and `dry_run` options. The result deserializes into a
+    /// `types::ReconfigureResult`.
+    pub fn reconfigure<ShardsT, ReplicasT>(
         self,
-        other: OtherT,
-    ) -> Expr<BoolOut, Term<(AstT, OtherT::Ast)>>
+        shards: ShardsT,
+        replicas: ReplicasT,
+    ) -> Expr<
+        ReconfigureResultOut,
+        Term<
+            (AstT,),
+            ReconfigureOptions<Expr<ShardsT::Out, ShardsT::Ast>, Expr<ReplicasT::Out, ReplicasT::Ast>>,
+        >,
+    >
     where
-        OutT: IsBool,
-        OtherT::Out: IsBool,
-        OtherT: IntoExpr,
+        OutT: IsDbOrTable,
+        ShardsT: IntoExpr,
+        ShardsT::Out: IsNumber,
+        ReplicasT: IntoExpr,
+        ReplicasT::Out: IsReplicas,
     {
-        Expr::raw(term(term::AND, (self.ast, other.into_ast())))
+        let reconfigure = term::<_, ReconfigureOptions>(term::RECONFIGURE, (self.ast,));
+        let reconfigure = WithOption::<ShardsOption, _>::with_option(reconfigure, shards);
+        Expr::raw(WithOption::<ReplicasOption, _>::with_option(reconfigure, replicas))
     }
 
-    // FIXME: Implement or
-    pub fn or<OtherT>(
+    /// Repair a table which lost a majority of its replicas (or all the tables of a database),
+    /// given a mode of `"unsafe_rollback"`, `"unsafe_rollback_or_erase"` or
+    /// `"_debug_recommit"`. Accepts the `dry_run` option. The result deserializes into a
+    /// `types::ReconfigureResult`.
+    pub fn emergency_repair<ModeT>(
         self,
-        other: OtherT,
-    ) -> Expr<BoolOut, Term<(AstT, OtherT::Ast)>>
+        mode: ModeT,
+    ) -> Expr<
+        ReconfigureResultOut,
+        Term<(AstT,), EmergencyRepairOptions<Expr<ModeT::Out, ModeT::Ast>>>,
+    >
     where
-        OutT: IsBool,
-        OtherT::Out: IsBool,
-        OtherT: IntoExpr,
+        OutT: IsDbOrTable,
+        ModeT: IntoExpr,
+        ModeT::Out: IsString,
     {
-        Expr::raw(term(term::OR, (self.ast, other.into_ast())))
+        let repair = term::<_, EmergencyRepairOptions>(term::RECONFIGURE, (self.ast,));
+        Expr::raw(WithOption::<EmergencyRepairOption, _>::with_option(repair, mode))
     }
 
-    /// Test if two or more values are equal.
-    /// FIXME: Support more args.
-    pub fn eq<OtherT>(self, other: OtherT) -> Expr<BoolOut, Term<(AstT, OtherT::Ast)>>
+    /// Sets the `primary_replica_tag` option for `reconfigure`, expects a string: the server tag
+    /// to pick primary replicas from.
+    pub fn with_primary_replica_tag<TagT>(self, tag: TagT) -> Expr<OutT, AstT::WithOption>
     where
-        OutT: IsEqualComparable<OtherT::Out>,
-        OtherT: IntoExpr,
+        AstT: WithOption<PrimaryReplicaTagOption, TagT>,
     {
-        Expr::raw(term(term::EQ, (self.ast, other.into_ast())))
+        Expr::raw(self.ast.with_option(tag))
     }
 
-    /// Test if two or more values are not equal.
-    /// FIXME: Support more args.
-    pub fn ne<OtherT>(self, other: OtherT) -> Expr<BoolOut, Term<(AstT, OtherT::Ast)>>
+    /// Sets the `nonvoting_replica_tags` option for `reconfigure`, expects an array of strings:
+    /// the server tags whose replicas don't vote in elections.
+    pub fn with_nonvoting_replica_tags<TagsT>(self, tags: TagsT) -> Expr<OutT, AstT::WithOption>
     where
-        OutT: IsEqualComparable<OtherT::Out>,
-        OtherT: IntoExpr,
+        AstT: WithOption<NonvotingReplicaTagsOption, TagsT>,
     {
-        Expr::raw(term(term::NE, (self.ast, other.into_ast())))
-    }
-
-    // FIXME: Implement gt
-    // FIXME: Implement ge
-    // FIXME: Implement lt
-    // FIXME: Implement le
-    // FIXME: Implement not
-    // FIXME: Implement random
-    // FIXME: Implement round
-    // FIXME: Implement ceil
-    // FIXME: Implement floor
-
-    // FIXME: Implement now
-    // FIXME: Implement time
-    // FIXME: Implement epochTime
-    // FIXME: Implement iso8601
-    // FIXME: Implement inTimezone
-    // FIXME: Implement timezone
-    // FIXME: Implement during
-    // FIXME: Implement date
-    // FIXME: Implement timeofday
-    // FIXME: Implement year
-    // FIXME: Implement month
-    // FIXME: Implement day
-    // FIXME: Implement dayOfWeek
-    // FIXME: Implement dayOfYear
-    // FIXME: Implement hours
-    // FIXME: Implement minutes
-    // FIXME: Implement seconds
-    // FIXME: Implement to_iso8601
-    // FIXME: Implement toEpochTime
-
-    // FIXME: Implement do
-    // FIXME: Implement branch
-    // FIXME: Implement forEach
-    // FIXME: Implement range
-    // FIXME: Implement error
-    // FIXME: Implement default
-    // FIXME: Implement js
-    // FIXME: Implement coerce_to (potentially as multiple functions)
-    // FIXME: Implement type_of
-    // FIXME: Implement json
-    // FIXME: Implement to_json
+        Expr::raw(self.ast.with_option(tags))
+    }
 
-    // FIXME: Implement http
-    // FIXME: Implement uuid
+    /// Sets the `dry_run` option for `reconfigure` and `emergency_repair`, expects a bool: whether
+    /// to only return the changes which would be made, without applying them.
+    pub fn with_dry_run<DryRunT>(self, dry_run: DryRunT) -> Expr<OutT, AstT::WithOption>
+    where
+        AstT: WithOption<DryRunOption, DryRunT>,
+    {
+        Expr::raw(self.ast.with_option(dry_run))
+    }
 
-    // FIXME: Implement grant
-    // FIXME: Implement config
-    // FIXME: Implement rebalance
-    // FIXME: Implement reconfigure
-    // FIXME: Implement status
-    // FIXME: Implement wait
+    /// Rebalance the shards of a table (or of all the tables of a database), so they hold roughly
+    /// the same number of documents. The result deserializes into a `types::RebalanceResult`.
+    pub fn rebalance(self) -> Expr<RebalanceResultOut, Term<(AstT,)>>
+    where
+        OutT: IsDbOrTable,
+    {
+        Expr::raw(term(term::REBALANCE, (self.ast,)))
+    }
 
     pub fn as_number(self) -> Expr<NumberOut, AstT> {
         Expr::raw(self.ast)
@@ -756,6 +2584,18 @@ impl<OutT, AstT> Expr<OutT, AstT> {
         Expr::raw(self.ast)
     }
 
+    pub fn as_time(self) -> Expr<TimeOut, AstT> {
+        Expr::raw(self.ast)
+    }
+
+    pub fn as_array<OfT>(self) -> Expr<ArrayOut<OfT>, AstT> {
+        Expr::raw(self.ast)
+    }
+
+    pub fn as_null_or(self) -> Expr<NullOr<OutT>, AstT> {
+        Expr::raw(self.ast)
+    }
+
     pub fn items_as<OtherT>(self) -> Expr<OutT::Rebound, AstT> where OutT: Rebind<OtherT> {
         Expr::raw(self.ast)
     }
@@ -789,6 +2629,80 @@ impl<OutT, AstT> Expr<OutT, AstT> {
     }
 }
 
+macro_rules! impl_binary_op {
+    ($op:ident, $method:ident, $term:ident, $output:ty, $($bound:tt)+) => {
+        impl<OutT, AstT, RhsT> ops::$op<RhsT> for Expr<OutT, AstT>
+        where
+            RhsT: IntoExpr,
+            $($bound)+
+        {
+            type Output = Expr<$output, Term<(AstT, RhsT::Ast)>>;
+
+            fn $method(self, rhs: RhsT) -> Self::Output {
+                Expr::raw(term(term::$term, (self.ast, rhs.into_ast())))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, ADD, OutT::Output, OutT: CanAdd<RhsT::Out>);
+impl_binary_op!(Sub, sub, SUB, OutT::Output, OutT: CanSub<RhsT::Out>);
+impl_binary_op!(Mul, mul, MUL, NumberOut, OutT: IsNumber, RhsT::Out: IsNumber);
+impl_binary_op!(Div, div, DIV, NumberOut, OutT: IsNumber, RhsT::Out: IsNumber);
+impl_binary_op!(Rem, rem, MOD, NumberOut, OutT: IsNumber, RhsT::Out: IsNumber);
+impl_binary_op!(BitAnd, bitand, AND, BoolOut, OutT: IsBool, RhsT::Out: IsBool);
+impl_binary_op!(BitOr, bitor, OR, BoolOut, OutT: IsBool, RhsT::Out: IsBool);
+
+impl<OutT: IsBool, AstT> ops::Not for Expr<OutT, AstT> {
+    type Output = Expr<BoolOut, Term<(AstT,)>>;
+
+    fn not(self) -> Self::Output {
+        Expr::raw(term(term::NOT, (self.ast,)))
+    }
+}
+
+/// Operators with a Rust literal on the left-hand side, e.g. `1 + expr`.
+macro_rules! impl_literal_binary_op {
+    ($op:ident, $method:ident, $term:ident, $output:ty, $lhs:ty, $($bound:tt)+) => {
+        impl<'a, OutT, AstT> ops::$op<Expr<OutT, AstT>> for $lhs
+        where
+            $($bound)+
+        {
+            type Output = Expr<$output, Term<($lhs, AstT)>>;
+
+            fn $method(self, rhs: Expr<OutT, AstT>) -> Self::Output {
+                Expr::raw(term(term::$term, (self, rhs.ast)))
+            }
+        }
+    };
+}
+
+macro_rules! impl_number_literal_ops {
+    ($($number:ty),+) => {
+        $(
+            impl_literal_binary_op!(
+                Add, add, ADD, <NumberOut as CanAdd<OutT>>::Output, $number, NumberOut: CanAdd<OutT>
+            );
+            impl_literal_binary_op!(
+                Sub, sub, SUB, <NumberOut as CanSub<OutT>>::Output, $number, NumberOut: CanSub<OutT>
+            );
+            impl_literal_binary_op!(Mul, mul, MUL, NumberOut, $number, OutT: IsNumber);
+            impl_literal_binary_op!(Div, div, DIV, NumberOut, $number, OutT: IsNumber);
+            impl_literal_binary_op!(Rem, rem, MOD, NumberOut, $number, OutT: IsNumber);
+        )+
+    };
+}
+
+impl_number_literal_ops!(u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
+impl_literal_binary_op!(
+    Add, add, ADD, <StringOut as CanAdd<OutT>>::Output, &'a str, StringOut: CanAdd<OutT>
+);
+impl_literal_binary_op!(
+    Add, add, ADD, <StringOut as CanAdd<OutT>>::Output, String, StringOut: CanAdd<OutT>
+);
+impl_literal_binary_op!(BitAnd, bitand, AND, BoolOut, bool, OutT: IsBool);
+impl_literal_binary_op!(BitOr, bitor, OR, BoolOut, bool, OutT: IsBool);
+
 impl<OutT, AstT: Serialize> Serialize for Expr<OutT, AstT> {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
         self.ast.serialize(serializer)
@@ -808,7 +2722,7 @@ pub struct Term<ArgsT, OptionsT: Options = NoOptions>(
 );
 
 
-#[derive(Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct Null;
 impl Serialize for Null {
     fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
@@ -883,6 +2797,9 @@ impl_datum!(StringOut, ref str);
 impl_datum!(NumberOut, u8, u16, u32, u64, i8, i16, i32, i64, f32, f64);
 impl_datum!(BoolOut, bool);
 impl_datum!(NullOut, Null);
+impl_datum!(TimeOut, ReqlTime);
+impl_datum!(GeometryOut, Geometry);
+impl_datum!(BinaryOut, ReqlBinary);
 
 macro_rules! impl_datum_fixed_array {
     ($($len:expr),+) => {
@@ -972,6 +2889,12 @@ pub enum StringOut {}
 pub struct ArrayOut<OfT>(PhantomData<*const OfT>);
 #[derive(Copy, Clone, Debug)]
 pub struct SelectionOut<OfT>(PhantomData<*const OfT>);
+/// A table sorted by `order_by_index`.
+#[derive(Copy, Clone, Debug)]
+pub struct OrderedSelectionOut<OfT>(PhantomData<*const OfT>);
+/// An `OrderedSelectionOut` cut short by `limit`, which `changes` accepts.
+#[derive(Copy, Clone, Debug)]
+pub struct OrderedLimitOut<OfT>(PhantomData<*const OfT>);
 #[derive(Copy, Clone, Debug)]
 pub struct SingleSelectionOut<OfT>(PhantomData<*const OfT>);
 #[derive(Copy, Clone, Debug)]
@@ -979,9 +2902,25 @@ pub struct StreamOut<OfT>(PhantomData<*const OfT>);
 #[derive(Copy, Clone, Debug)]
 pub struct FunctionOut<ArgsT, ReturnT>(PhantomData<*const (ArgsT, ReturnT)>);
 #[derive(Copy, Clone, Debug)]
+pub struct GroupedStreamOut<KeyT, OfT>(PhantomData<*const (KeyT, OfT)>);
+#[derive(Copy, Clone, Debug)]
+pub struct GroupedDataOut<KeyT, OfT>(PhantomData<*const (KeyT, OfT)>);
+#[derive(Copy, Clone, Debug)]
 pub enum ObjectOut {}
+
+#[derive(Copy, Clone, Debug)]
+pub struct OrderingOut<KeyT>(PhantomData<*const KeyT>);
 #[derive(Copy, Clone, Debug)]
 pub enum BoolOut {}
+
+#[derive(Copy, Clone, Debug)]
+pub enum TimeOut {}
+
+#[derive(Copy, Clone, Debug)]
+pub enum GeometryOut {}
+
+#[derive(Copy, Clone, Debug)]
+pub enum BinaryOut {}
 #[derive(Copy, Clone, Debug)]
 pub enum NumberOut {}
 #[derive(Copy, Clone, Debug)]
@@ -990,12 +2929,58 @@ pub enum NullOut {}
 pub enum AnyOut {}
 
 #[derive(Copy, Clone, Debug)]
-pub enum TableOut {}
+pub enum TableOut {}
+#[derive(Copy, Clone, Debug)]
+pub enum DbOut {}
+
+#[derive(Copy, Clone, Debug)]
+pub struct NullOr<OfT>(PhantomData<*const OfT>);
+
+/// The result of a write query such as `insert`, `update` or `delete`.
+#[derive(Copy, Clone, Debug)]
+pub enum WriteOut {}
+
+/// The status of a secondary index, deserializes into a `types::IndexStatus`.
+#[derive(Copy, Clone, Debug)]
+pub enum IndexStatusOut {}
+
+/// The configuration of a database, deserializes into a `types::DbConfig`.
+#[derive(Copy, Clone, Debug)]
+pub enum DbConfigOut {}
+
+/// The configuration of a table, deserializes into a `types::TableConfig`.
+#[derive(Copy, Clone, Debug)]
+pub enum TableConfigOut {}
+
+/// The status of a table, deserializes into a `types::TableStatus`.
+#[derive(Copy, Clone, Debug)]
+pub enum TableStatusOut {}
+
+/// The result of `wait`, deserializes into a `types::WaitResult`.
+#[derive(Copy, Clone, Debug)]
+pub enum WaitResultOut {}
+
+/// The result of `reconfigure` and `emergency_repair`, deserializes into a
+/// `types::ReconfigureResult`.
+#[derive(Copy, Clone, Debug)]
+pub enum ReconfigureResultOut {}
+
+/// The result of `rebalance`, deserializes into a `types::RebalanceResult`.
+#[derive(Copy, Clone, Debug)]
+pub enum RebalanceResultOut {}
+
+/// The result of `match_`, deserializes into a `types::RegexMatch`.
+#[derive(Copy, Clone, Debug)]
+pub enum MatchOut {}
+
+/// A `left`/`right` pair produced by `inner_join` or `eq_join`.
 #[derive(Copy, Clone, Debug)]
-pub enum DbOut {}
+pub struct JoinOut<LeftT, RightT>(PhantomData<*const (LeftT, RightT)>);
 
+/// A `left`/`right` pair produced by `outer_join`, where `right` is missing for elements which
+/// matched nothing.
 #[derive(Copy, Clone, Debug)]
-pub struct NullOr<OfT>(PhantomData<*const OfT>);
+pub struct OuterJoinOut<LeftT, RightT>(PhantomData<*const (LeftT, RightT)>);
 
 pub trait IsDb {}
 impl IsDb for DbOut {}
@@ -1003,9 +2988,55 @@ impl IsDb for DbOut {}
 pub trait IsTable {}
 impl IsTable for TableOut {}
 
+pub trait IsDbOrTable {
+    /// The output of `config`.
+    type Config;
+}
+impl IsDbOrTable for DbOut {
+    type Config = DbConfigOut;
+}
+impl IsDbOrTable for TableOut {
+    type Config = TableConfigOut;
+}
+
 pub trait IsObject {}
 impl IsObject for ObjectOut {}
 impl IsObject for SingleSelectionOut<ObjectOut> {}
+impl<LeftT, RightT> IsObject for JoinOut<LeftT, RightT> {}
+impl<LeftT, RightT> IsObject for OuterJoinOut<LeftT, RightT> {}
+impl IsObject for MatchOut {}
+impl IsObject for WriteOut {}
+impl IsObject for IndexStatusOut {}
+impl IsObject for DbConfigOut {}
+impl IsObject for TableConfigOut {}
+impl IsObject for SingleSelectionOut<DbConfigOut> {}
+impl IsObject for SingleSelectionOut<TableConfigOut> {}
+impl IsObject for TableStatusOut {}
+impl IsObject for WaitResultOut {}
+impl IsObject for ReconfigureResultOut {}
+impl IsObject for RebalanceResultOut {}
+
+/// Write queries, or arrays of them, which `for_each` accepts.
+pub trait IsWrite {}
+impl IsWrite for WriteOut {}
+impl IsWrite for ArrayOut<WriteOut> {}
+
+/// Values which may be null, which `default` replaces.
+pub trait CanDefault<WithT> {
+    type Output;
+}
+impl<OfT> CanDefault<OfT> for NullOr<OfT> {
+    type Output = OfT;
+}
+impl<OfT> CanDefault<NullOr<OfT>> for NullOr<OfT> {
+    type Output = NullOr<OfT>;
+}
+impl<WithT> CanDefault<WithT> for AnyOut {
+    type Output = AnyOut;
+}
+
+pub trait IsArray: IsSequence {}
+impl<OfT> IsArray for ArrayOut<OfT> {}
 
 pub trait IsString {}
 impl IsString for StringOut {}
@@ -1019,45 +3050,132 @@ pub trait IsBool {}
 impl IsBool for BoolOut {}
 impl IsBool for SingleSelectionOut<BoolOut> {}
 
+pub trait IsTime {}
+impl IsTime for TimeOut {}
+impl IsTime for SingleSelectionOut<TimeOut> {}
+
+pub trait IsGeometry {}
+impl IsGeometry for GeometryOut {}
+impl IsGeometry for SingleSelectionOut<GeometryOut> {}
+
+pub trait IsPoint {}
+impl IsPoint for GeometryOut {}
+impl IsPoint for ArrayOut<NumberOut> {}
+
+pub trait IsGeometryOrGeometrySequence {
+    type BoolOrSequence;
+}
+impl IsGeometryOrGeometrySequence for GeometryOut {
+    type BoolOrSequence = BoolOut;
+}
+impl IsGeometryOrGeometrySequence for SingleSelectionOut<GeometryOut> {
+    type BoolOrSequence = BoolOut;
+}
+impl<OfT: IsGeometry> IsGeometryOrGeometrySequence for ArrayOut<OfT> {
+    type BoolOrSequence = ArrayOut<OfT>;
+}
+impl<OfT: IsGeometry> IsGeometryOrGeometrySequence for StreamOut<OfT> {
+    type BoolOrSequence = StreamOut<OfT>;
+}
+
 pub trait IsKey {}
 impl IsKey for StringOut {}
 impl IsKey for NumberOut {}
 impl IsKey for SingleSelectionOut<StringOut> {}
 impl IsKey for SingleSelectionOut<NumberOut> {}
+impl IsKey for TimeOut {}
 
 pub trait IsSelector {}
 impl IsSelector for StringOut {}
 impl IsSelector for ObjectOut {}
+impl IsSelector for AnyOut {}
+impl<OfT: IsSelector> IsSelector for ArrayOut<OfT> {}
 
 pub trait IsIndexKey {}
 impl<OfT> IsIndexKey for ArrayOut<OfT> {}
 impl<OfT> IsIndexKey for SelectionOut<OfT> {}
+impl<OfT> IsIndexKey for OrderedSelectionOut<OfT> {}
+impl<OfT> IsIndexKey for OrderedLimitOut<OfT> {}
 impl<OfT> IsIndexKey for SingleSelectionOut<OfT> {}
 impl<OfT> IsIndexKey for StreamOut<OfT> {}
 impl IsIndexKey for BoolOut {}
 impl IsIndexKey for NumberOut {}
 impl IsIndexKey for StringOut {}
+impl IsIndexKey for TimeOut {}
+impl IsIndexKey for BinaryOut {}
 
+/// Objects, and sequences of objects, which terms such as `pluck` and `merge` apply to element by
+/// element.
 pub trait IsObjectOrObjectSequence {
     type AnyOrAnySequence;
+    /// The type of the object (or of each object in the sequence).
+    type Object;
+    /// The same shape with objects of unknown fields: `ObjectOut` or a sequence of them.
+    type ObjectOrObjectSequence;
+    /// `BoolOut` for an object, the same sequence for a sequence of objects.
+    type BoolOrSequence;
 }
 impl IsObjectOrObjectSequence for ObjectOut {
     type AnyOrAnySequence = AnyOut;
+    type Object = ObjectOut;
+    type ObjectOrObjectSequence = ObjectOut;
+    type BoolOrSequence = BoolOut;
+}
+/// Untyped values, such as fields of a document, may be objects too; this lets field accesses
+/// chain, as in `doc.g("a").g("b")`.
+impl IsObjectOrObjectSequence for AnyOut {
+    type AnyOrAnySequence = AnyOut;
+    type Object = ObjectOut;
+    type ObjectOrObjectSequence = ObjectOut;
+    type BoolOrSequence = BoolOut;
 }
 impl<OfT: IsObject> IsObjectOrObjectSequence for SingleSelectionOut<OfT> {
     type AnyOrAnySequence = AnyOut;
+    type Object = OfT;
+    type ObjectOrObjectSequence = ObjectOut;
+    type BoolOrSequence = BoolOut;
 }
 impl<OfT: IsObject> IsObjectOrObjectSequence for SelectionOut<OfT> {
     type AnyOrAnySequence = SelectionOut<AnyOut>;
+    type Object = OfT;
+    type ObjectOrObjectSequence = StreamOut<ObjectOut>;
+    type BoolOrSequence = SelectionOut<OfT>;
+}
+impl<OfT: IsObject> IsObjectOrObjectSequence for OrderedSelectionOut<OfT> {
+    type AnyOrAnySequence = SelectionOut<AnyOut>;
+    type Object = OfT;
+    type ObjectOrObjectSequence = StreamOut<ObjectOut>;
+    type BoolOrSequence = SelectionOut<OfT>;
+}
+impl<OfT: IsObject> IsObjectOrObjectSequence for OrderedLimitOut<OfT> {
+    type AnyOrAnySequence = SelectionOut<AnyOut>;
+    type Object = OfT;
+    type ObjectOrObjectSequence = StreamOut<ObjectOut>;
+    type BoolOrSequence = SelectionOut<OfT>;
 }
 impl<OfT: IsObject> IsObjectOrObjectSequence for StreamOut<OfT> {
     type AnyOrAnySequence = StreamOut<AnyOut>;
+    type Object = OfT;
+    type ObjectOrObjectSequence = StreamOut<ObjectOut>;
+    type BoolOrSequence = StreamOut<OfT>;
 }
 impl<OfT: IsObject> IsObjectOrObjectSequence for ArrayOut<OfT> {
     type AnyOrAnySequence = ArrayOut<AnyOut>;
+    type Object = OfT;
+    type ObjectOrObjectSequence = ArrayOut<ObjectOut>;
+    type BoolOrSequence = ArrayOut<OfT>;
+}
+impl<KeyT, OfT: IsObject> IsObjectOrObjectSequence for GroupedStreamOut<KeyT, OfT> {
+    type AnyOrAnySequence = GroupedStreamOut<KeyT, AnyOut>;
+    type Object = OfT;
+    type ObjectOrObjectSequence = GroupedStreamOut<KeyT, ObjectOut>;
+    type BoolOrSequence = GroupedStreamOut<KeyT, OfT>;
 }
 impl IsObjectOrObjectSequence for TableOut {
     type AnyOrAnySequence = SelectionOut<AnyOut>;
+    type Object = ObjectOut;
+    type ObjectOrObjectSequence = StreamOut<ObjectOut>;
+    type BoolOrSequence = SelectionOut<ObjectOut>;
 }
 
 pub trait IsSequence {
@@ -1105,20 +3223,219 @@ impl<ToT, OfT> Rebind<ToT> for SelectionOut<OfT> {
     type Rebound = StreamOut<ToT>;
 }
 
+impl<OfT> IsSequence for OrderedSelectionOut<OfT> {
+    type SequenceItem = OfT;
+    type Select = SingleSelectionOut<OfT>;
+}
+
+impl<ToT, OfT> Rebind<ToT> for OrderedSelectionOut<OfT> {
+    type Rebound = StreamOut<ToT>;
+}
+
+impl<OfT> IsSequence for OrderedLimitOut<OfT> {
+    type SequenceItem = OfT;
+    type Select = SingleSelectionOut<OfT>;
+}
+
+impl<ToT, OfT> Rebind<ToT> for OrderedLimitOut<OfT> {
+    type Rebound = StreamOut<ToT>;
+}
+
+impl<KeyT, OfT> IsSequence for GroupedStreamOut<KeyT, OfT> {
+    type SequenceItem = OfT;
+    type Select = GroupedDataOut<KeyT, OfT>;
+}
+
+impl<ToT, KeyT, OfT> Rebind<ToT> for GroupedStreamOut<KeyT, OfT> {
+    type Rebound = GroupedStreamOut<KeyT, ToT>;
+}
+
+/// The output of an aggregation producing a `ResultT` over a sequence: a single result for plain
+/// sequences, one per group for grouped streams.
+pub trait Aggregate<ResultT>: IsSequence {
+    type Aggregated;
+}
+
+impl<ResultT> Aggregate<ResultT> for TableOut {
+    type Aggregated = ResultT;
+}
+
+impl<ResultT, OfT> Aggregate<ResultT> for ArrayOut<OfT> {
+    type Aggregated = ResultT;
+}
+
+impl<ResultT, OfT> Aggregate<ResultT> for StreamOut<OfT> {
+    type Aggregated = ResultT;
+}
+
+impl<ResultT, OfT> Aggregate<ResultT> for SelectionOut<OfT> {
+    type Aggregated = ResultT;
+}
+
+impl<ResultT, OfT> Aggregate<ResultT> for OrderedSelectionOut<OfT> {
+    type Aggregated = ResultT;
+}
+
+impl<ResultT, OfT> Aggregate<ResultT> for OrderedLimitOut<OfT> {
+    type Aggregated = ResultT;
+}
+
+impl<ResultT, KeyT, OfT> Aggregate<ResultT> for GroupedStreamOut<KeyT, OfT> {
+    type Aggregated = GroupedDataOut<KeyT, ResultT>;
+}
+
+/// The output of `limit`: the same sequence, except that a limited `order_by_index` becomes an
+/// `OrderedLimitOut`.
+pub trait Limit: IsSequence {
+    type Limited: IsSequence<SequenceItem = Self::SequenceItem>;
+}
+
+impl Limit for TableOut {
+    type Limited = TableOut;
+}
+
+impl<OfT> Limit for ArrayOut<OfT> {
+    type Limited = ArrayOut<OfT>;
+}
+
+impl<OfT> Limit for StreamOut<OfT> {
+    type Limited = StreamOut<OfT>;
+}
+
+impl<OfT> Limit for SelectionOut<OfT> {
+    type Limited = SelectionOut<OfT>;
+}
+
+impl<OfT> Limit for OrderedSelectionOut<OfT> {
+    type Limited = OrderedLimitOut<OfT>;
+}
+
+impl<OfT> Limit for OrderedLimitOut<OfT> {
+    type Limited = OrderedLimitOut<OfT>;
+}
+
+impl<KeyT, OfT> Limit for GroupedStreamOut<KeyT, OfT> {
+    type Limited = GroupedStreamOut<KeyT, OfT>;
+}
+
+pub trait IsGrouped {}
+impl<KeyT, OfT> IsGrouped for GroupedStreamOut<KeyT, OfT> {}
+impl<KeyT, OfT> IsGrouped for GroupedDataOut<KeyT, OfT> {}
+
+pub trait IsJoin {
+    type Left;
+    type Right;
+}
+impl<LeftT, RightT> IsJoin for JoinOut<LeftT, RightT> {
+    type Left = LeftT;
+    type Right = RightT;
+}
+impl<LeftT, RightT> IsJoin for OuterJoinOut<LeftT, RightT> {
+    type Left = LeftT;
+    type Right = NullOr<RightT>;
+}
+
+/// How `right` reads the `right` field of a join pair: as is for `JoinOut`, or defaulted to null
+/// for `OuterJoinOut`.
+pub trait JoinRight<AstT>: IsJoin {
+    type RightAst;
+    fn right_ast(field: Term<(AstT, &'static str)>) -> Self::RightAst;
+}
+impl<AstT, LeftT, RightT> JoinRight<AstT> for JoinOut<LeftT, RightT> {
+    type RightAst = Term<(AstT, &'static str)>;
+    fn right_ast(field: Term<(AstT, &'static str)>) -> Self::RightAst {
+        field
+    }
+}
+impl<AstT, LeftT, RightT> JoinRight<AstT> for OuterJoinOut<LeftT, RightT> {
+    type RightAst = Term<(Term<(AstT, &'static str)>, Null)>;
+    fn right_ast(field: Term<(AstT, &'static str)>) -> Self::RightAst {
+        term(term::DEFAULT, (field, Null))
+    }
+}
+
+pub trait IsOrderKey<ItemT> {}
+impl<ItemT> IsOrderKey<ItemT> for StringOut {}
+impl<ItemT, ReturnT> IsOrderKey<ItemT> for FunctionOut<(ItemT,), ReturnT> {}
+impl<ItemT, KeyT: IsOrderKey<ItemT>> IsOrderKey<ItemT> for OrderingOut<KeyT> {}
+
+pub trait IsIndexOrdering {}
+impl IsIndexOrdering for StringOut {}
+impl IsIndexOrdering for OrderingOut<StringOut> {}
+
+pub trait IsChangefeedSource {}
+impl IsChangefeedSource for TableOut {}
+impl<OfT> IsChangefeedSource for SelectionOut<OfT> {}
+impl<OfT> IsChangefeedSource for SingleSelectionOut<OfT> {}
+impl<OfT> IsChangefeedSource for OrderedLimitOut<OfT> {}
+
+/// Values of the `replicas` option: a number, or an object mapping server tags to numbers.
+pub trait IsReplicas {}
+impl IsReplicas for NumberOut {}
+impl IsReplicas for ObjectOut {}
+
+/// Arrays of strings, e.g. the server tags of the `nonvoting_replica_tags` option.
+pub trait IsStringArray {}
+impl IsStringArray for ArrayOut<StringOut> {}
+
+pub trait IsBoolOrString {}
+impl IsBoolOrString for BoolOut {}
+impl IsBoolOrString for StringOut {}
+
+/// Values of the `conflict` option: a strategy name or a resolver function.
+pub trait IsConflictResolver {}
+impl IsConflictResolver for StringOut {}
+impl IsConflictResolver for FunctionOut<(AnyOut, ObjectOut, ObjectOut), ObjectOut> {}
+
+pub trait IsBoolOrNumber {}
+impl IsBoolOrNumber for BoolOut {}
+impl IsBoolOrNumber for NumberOut {}
+impl IsBoolOrNumber for SingleSelectionOut<BoolOut> {}
+impl IsBoolOrNumber for SingleSelectionOut<NumberOut> {}
+
 pub trait IsEqualComparable<WithT> {}
 impl IsEqualComparable<BoolOut> for BoolOut {}
 impl IsEqualComparable<NumberOut> for NumberOut {}
 impl IsEqualComparable<StringOut> for StringOut {}
 impl IsEqualComparable<ObjectOut> for ObjectOut {}
+impl IsEqualComparable<TimeOut> for TimeOut {}
+impl IsEqualComparable<GeometryOut> for GeometryOut {}
+impl IsEqualComparable<BinaryOut> for BinaryOut {}
+impl<OfT> IsEqualComparable<NullOut> for NullOr<OfT> {}
+impl<OfT> IsEqualComparable<NullOut> for SingleSelectionOut<OfT> {}
 impl<WithT, OfT> IsEqualComparable<ArrayOut<WithT>> for ArrayOut<OfT>
 where
     OfT: IsEqualComparable<WithT>,
 {
 }
 
+/// Types which can be ordered against `WithT` by `gt`, `ge`, `lt` and `le`.
+pub trait IsOrderComparable<WithT> {}
+impl IsOrderComparable<BoolOut> for BoolOut {}
+impl IsOrderComparable<NumberOut> for NumberOut {}
+impl IsOrderComparable<StringOut> for StringOut {}
+impl IsOrderComparable<TimeOut> for TimeOut {}
+impl IsOrderComparable<BinaryOut> for BinaryOut {}
+impl<WithT, OfT> IsOrderComparable<ArrayOut<WithT>> for ArrayOut<OfT>
+where
+    OfT: IsOrderComparable<WithT>,
+{
+}
+
+/// Values `replace_with` may return: a document, or null to delete it.
+pub trait IsReplacement {}
+impl IsReplacement for ObjectOut {}
+impl IsReplacement for NullOut {}
+impl<OfT: IsObject> IsReplacement for NullOr<OfT> {}
+
 pub trait IsSelection<OfT> {}
 impl<OfT> IsSelection<OfT> for SelectionOut<OfT> {}
+impl<OfT> IsSelection<OfT> for OrderedSelectionOut<OfT> {}
+impl<OfT> IsSelection<OfT> for OrderedLimitOut<OfT> {}
 impl<OfT> IsSelection<OfT> for SingleSelectionOut<OfT> {}
+/// Configurations are updated like any other document.
+impl IsSelection<ObjectOut> for SingleSelectionOut<DbConfigOut> {}
+impl IsSelection<ObjectOut> for SingleSelectionOut<TableConfigOut> {}
 impl<ObjectT> IsSelection<ObjectT> for TableOut {}
 
 pub trait CanAdd<WithT> {
@@ -1133,10 +3450,36 @@ impl CanAdd<StringOut> for StringOut {
 impl<WithT, OfT> CanAdd<ArrayOut<WithT>> for ArrayOut<OfT> {
     type Output = ArrayOut<AnyOut>;
 }
+impl CanAdd<NumberOut> for TimeOut {
+    type Output = TimeOut;
+}
+
+pub trait CanSub<WithT> {
+    type Output;
+}
+impl<WithT: IsNumber> CanSub<WithT> for NumberOut {
+    type Output = NumberOut;
+}
+impl<WithT: IsNumber> CanSub<WithT> for SingleSelectionOut<NumberOut> {
+    type Output = NumberOut;
+}
+impl<WithT: IsNumber> CanSub<WithT> for TimeOut {
+    type Output = TimeOut;
+}
+impl CanSub<TimeOut> for TimeOut {
+    type Output = NumberOut;
+}
 
 pub type Var<OutT> = Expr<OutT, Term<(usize,)>>;
 
-const NEXT_VAR_ID: AtomicUsize = ATOMIC_USIZE_INIT;
+/// The index function built by `index_create_compound`: a `FUNC` of one document, returning the
+/// array of its values for each field.
+pub type CompoundIndexFunction<FieldAstT> =
+    Term<(Term<(usize,)>, Term<Vec<Term<(Term<(usize,)>, FieldAstT)>>>)>;
+
+/// Variable ids are unique for the lifetime of the process, so closures nested inside one another
+/// (or spliced into the same query) never capture each other's variables.
+static NEXT_VAR_ID: AtomicUsize = AtomicUsize::new(0);
 fn fresh_var<OutT>() -> Var<OutT> {
     Expr::raw(term(
         term::VAR,
@@ -1145,28 +3488,217 @@ fn fresh_var<OutT>() -> Var<OutT> {
 }
 
 pub trait IntoFunctionExpr<ArgsT, ReturnT> {
-    type FunctionAst;
+    type FunctionAst: Serialize;
 
     fn into_function_expr(self) -> Expr<FunctionOut<ArgsT, ReturnT>, Self::FunctionAst>;
 }
 
-impl<Arg1T, ReturnRawT, FunctionT> IntoFunctionExpr<(Arg1T,), ReturnRawT::Out> for FunctionT
+macro_rules! impl_into_function_expr {
+    (@usize $arg:ident) => { usize };
+    ($($arg:ident: $var:ident),+) => {
+        impl<$($arg,)+ ReturnRawT, FunctionT> IntoFunctionExpr<($($arg,)+), ReturnRawT::Out>
+            for FunctionT
+        where
+            FunctionT: FnOnce($(Var<$arg>),+) -> ReturnRawT,
+            ReturnRawT: IntoExpr,
+        {
+            type FunctionAst =
+                Term<(Term<($(impl_into_function_expr!(@usize $arg),)+)>, ReturnRawT::Ast)>;
+
+            fn into_function_expr(
+                self,
+            ) -> Expr<FunctionOut<($($arg,)+), ReturnRawT::Out>, Self::FunctionAst> {
+                $(let $var = fresh_var::<$arg>();)+
+                let var_ids = ($(($var.ast.1).0,)+);
+                Expr::raw(term(
+                    term::FUNC,
+                    (term(term::MAKE_ARRAY, var_ids), (self)($($var),+).into_ast()),
+                ))
+            }
+        }
+    };
+}
+
+impl_into_function_expr!(Arg1T: var1);
+impl_into_function_expr!(Arg1T: var1, Arg2T: var2);
+impl_into_function_expr!(Arg1T: var1, Arg2T: var2, Arg3T: var3);
+impl_into_function_expr!(Arg1T: var1, Arg2T: var2, Arg3T: var3, Arg4T: var4);
+impl_into_function_expr!(Arg1T: var1, Arg2T: var2, Arg3T: var3, Arg4T: var4, Arg5T: var5);
+
+/// A single sort key for `order_by` on a sequence of `ItemT`.
+pub trait IntoOrderKey<ItemT> {
+    type Ast: Serialize;
+    fn into_order_key(self) -> Self::Ast;
+}
+
+impl<ItemT, OutT: IsOrderKey<ItemT>, AstT: Serialize> IntoOrderKey<ItemT> for Expr<OutT, AstT> {
+    type Ast = AstT;
+    fn into_order_key(self) -> Self::Ast {
+        self.ast
+    }
+}
+
+impl<'a, ItemT> IntoOrderKey<ItemT> for &'a str {
+    type Ast = Self;
+    fn into_order_key(self) -> Self::Ast {
+        self
+    }
+}
+
+impl<ItemT> IntoOrderKey<ItemT> for String {
+    type Ast = Self;
+    fn into_order_key(self) -> Self::Ast {
+        self
+    }
+}
+
+/// One sort key, or a tuple of them, for `order_by`; produces the arguments of the `ORDER_BY`
+/// term, with the sequence first.
+pub trait IntoOrderKeys<ItemT, SequenceAstT> {
+    type Args: Serialize;
+    fn into_order_args(self, sequence: SequenceAstT) -> Self::Args;
+}
+
+impl<ItemT, SequenceAstT, OutT, AstT> IntoOrderKeys<ItemT, SequenceAstT> for Expr<OutT, AstT>
 where
-    FunctionT: FnOnce(Var<Arg1T>) -> ReturnRawT,
-    ReturnRawT: IntoExpr,
+    SequenceAstT: Serialize,
+    Self: IntoOrderKey<ItemT>,
 {
-    type FunctionAst = Term<(Term<(usize,)>, ReturnRawT::Ast)>;
+    type Args = (SequenceAstT, <Self as IntoOrderKey<ItemT>>::Ast);
+    fn into_order_args(self, sequence: SequenceAstT) -> Self::Args {
+        (sequence, self.into_order_key())
+    }
+}
 
-    fn into_function_expr(self) -> Expr<FunctionOut<(Arg1T,), ReturnRawT::Out>, Self::FunctionAst> {
-        let var = fresh_var();
-        let var_id = (var.ast.1).0;
-        Expr::raw(term(
-            term::FUNC,
-            (term(term::MAKE_ARRAY, (var_id,)), (self)(var).into_ast()),
-        ))
+impl<'a, ItemT, SequenceAstT: Serialize> IntoOrderKeys<ItemT, SequenceAstT> for &'a str {
+    type Args = (SequenceAstT, Self);
+    fn into_order_args(self, sequence: SequenceAstT) -> Self::Args {
+        (sequence, self)
+    }
+}
+
+impl<ItemT, SequenceAstT: Serialize> IntoOrderKeys<ItemT, SequenceAstT> for String {
+    type Args = (SequenceAstT, Self);
+    fn into_order_args(self, sequence: SequenceAstT) -> Self::Args {
+        (sequence, self)
     }
 }
 
+macro_rules! impl_into_order_keys {
+    ($($key:ident: $name:ident),+) => {
+        impl<ItemT, SequenceAstT, $($key),+> IntoOrderKeys<ItemT, SequenceAstT> for ($($key,)+)
+        where
+            SequenceAstT: Serialize,
+            $($key: IntoOrderKey<ItemT>),+
+        {
+            type Args = (SequenceAstT, $($key::Ast),+);
+
+            fn into_order_args(self, sequence: SequenceAstT) -> Self::Args {
+                let ($($name,)+) = self;
+                (sequence, $($name.into_order_key()),+)
+            }
+        }
+    };
+}
+
+impl_into_order_keys!(Key1T: key1);
+impl_into_order_keys!(Key1T: key1, Key2T: key2);
+impl_into_order_keys!(Key1T: key1, Key2T: key2, Key3T: key3);
+impl_into_order_keys!(Key1T: key1, Key2T: key2, Key3T: key3, Key4T: key4);
+impl_into_order_keys!(Key1T: key1, Key2T: key2, Key3T: key3, Key4T: key4, Key5T: key5);
+
+/// A tuple of expressions with the same output type, for `r::args_of`.
+pub trait IntoArgsTuple: IntoAst {
+    type Item;
+}
+
+macro_rules! impl_into_args_tuple {
+    ($head:ident $(, $tail:ident)*) => {
+        impl<$head, $($tail),*> IntoArgsTuple for ($head, $($tail),*)
+        where
+            $head: IntoExpr,
+            $($tail: IntoExpr<Out = $head::Out>),*
+        {
+            type Item = $head::Out;
+        }
+    };
+}
+
+impl_into_args_tuple!(A);
+impl_into_args_tuple!(A, B);
+impl_into_args_tuple!(A, B, C);
+impl_into_args_tuple!(A, B, C, D);
+impl_into_args_tuple!(A, B, C, D, E);
+impl_into_args_tuple!(A, B, C, D, E, F);
+impl_into_args_tuple!(A, B, C, D, E, F, G);
+impl_into_args_tuple!(A, B, C, D, E, F, G, H);
+
+/// A tuple of key-value pairs for `r::object`; produces the arguments of the `OBJECT` term.
+pub trait IntoObjectPairs {
+    type Args: Serialize;
+    fn into_object_args(self) -> Self::Args;
+}
+
+macro_rules! impl_into_object_pairs {
+    ($($key:ident: $value:ident),+) => {
+        impl<$($key, $value),+> IntoObjectPairs for ($(($key, $value),)+)
+        where
+            $(
+                $key: IntoExpr,
+                $key::Out: IsString,
+                $value: IntoExpr,
+            )+
+        {
+            type Args = ($($key::Ast, $value::Ast),+);
+
+            #[allow(non_snake_case)]
+            fn into_object_args(self) -> Self::Args {
+                let ($(($key, $value),)+) = self;
+                ($($key.into_ast(), $value.into_ast()),+)
+            }
+        }
+    };
+}
+
+impl_into_object_pairs!(Key1T: Value1T);
+impl_into_object_pairs!(Key1T: Value1T, Key2T: Value2T);
+impl_into_object_pairs!(Key1T: Value1T, Key2T: Value2T, Key3T: Value3T);
+impl_into_object_pairs!(Key1T: Value1T, Key2T: Value2T, Key3T: Value3T, Key4T: Value4T);
+impl_into_object_pairs!(
+    Key1T: Value1T,
+    Key2T: Value2T,
+    Key3T: Value3T,
+    Key4T: Value4T,
+    Key5T: Value5T
+);
+impl_into_object_pairs!(
+    Key1T: Value1T,
+    Key2T: Value2T,
+    Key3T: Value3T,
+    Key4T: Value4T,
+    Key5T: Value5T,
+    Key6T: Value6T
+);
+impl_into_object_pairs!(
+    Key1T: Value1T,
+    Key2T: Value2T,
+    Key3T: Value3T,
+    Key4T: Value4T,
+    Key5T: Value5T,
+    Key6T: Value6T,
+    Key7T: Value7T
+);
+impl_into_object_pairs!(
+    Key1T: Value1T,
+    Key2T: Value2T,
+    Key3T: Value3T,
+    Key4T: Value4T,
+    Key5T: Value5T,
+    Key6T: Value6T,
+    Key7T: Value7T,
+    Key8T: Value8T
+);
+
 fn term<ArgsT, OptionsT: Default + Options>(term_type: u32, args: ArgsT) -> Term<ArgsT, OptionsT> {
     Term(term_type, args, OptionsT::default())
 }
@@ -1213,6 +3745,100 @@ where
 pub enum IndexOption {}
 pub enum LeftBoundOption {}
 pub enum RightBoundOption {}
+pub enum MultiOption {}
+pub enum GeoOption {}
+pub enum UnitOption {}
+pub enum GeoSystemOption {}
+pub enum MaxDistOption {}
+pub enum MaxResultsOption {}
+pub enum NumVerticesOption {}
+pub enum FillOption {}
+pub enum DefaultTimezoneOption {}
+pub enum OrderedOption {}
+pub enum FinalEmitOption {}
+pub enum SquashOption {}
+pub enum IncludeInitialOption {}
+pub enum IncludeStatesOption {}
+pub enum IncludeTypesOption {}
+pub enum ChangefeedQueueSizeOption {}
+pub enum FloatOption {}
+pub enum DurabilityOption {}
+pub enum ReturnChangesOption {}
+pub enum NonAtomicOption {}
+pub enum IgnoreWriteHookOption {}
+pub enum ConflictOption {}
+pub enum OverwriteOption {}
+pub enum WaitForOption {}
+pub enum TimeoutOption {}
+pub enum ShardsOption {}
+pub enum ReplicasOption {}
+pub enum PrimaryReplicaTagOption {}
+pub enum NonvotingReplicaTagsOption {}
+pub enum DryRunOption {}
+pub enum EmergencyRepairOption {}
+
+/// Declares an options struct with one type parameter per option, `()` while the option is unset,
+/// along with its `Options` impl and a `WithOption` impl to set each of the options once. An
+/// option's value can be any expression whose output implements the given trait.
+macro_rules! options {
+    (
+        $(#[$attribute:meta])*
+        pub struct $name:ident {
+            $($field:ident: $param:ident = $option:ident where $bound:path,)+
+        }
+    ) => {
+        $(#[$attribute])*
+        #[derive(Copy, Clone, Debug, Serialize, Default)]
+        pub struct $name<$($param: OptionValue = (),)+> {
+            $(#[serde(skip_serializing_if = "OptionValue::is_unset")] $field: $param,)+
+        }
+
+        impl<$($param: OptionValue,)+> Options for $name<$($param,)+> {
+            fn all_unset(&self) -> bool {
+                $(self.$field.is_unset() &&)+ true
+            }
+        }
+
+        impl_with_options!($name [] [$($field: $param = $option where $bound,)+]);
+    };
+}
+
+/// Implements `WithOption` for each option in the second list, keeping track of the options
+/// before it in the first one.
+macro_rules! impl_with_options {
+    ($name:ident [$($before_field:ident: $before:ident,)*] []) => {};
+    (
+        $name:ident
+        [$($before_field:ident: $before:ident,)*]
+        [
+            $field:ident: $param:ident = $option:ident where $bound:path,
+            $($after_field:ident: $after:ident = $after_option:ident where $after_bound:path,)*
+        ]
+    ) => {
+        impl<ValueT, $($before: OptionValue,)* $($after: OptionValue,)*> WithOption<$option, ValueT>
+            for $name<$($before,)* (), $($after,)*>
+        where
+            ValueT: IntoExpr,
+            ValueT::Out: $bound,
+        {
+            type WithOption = $name<$($before,)* Expr<ValueT::Out, ValueT::Ast>, $($after,)*>;
+
+            fn with_option(self, value: ValueT) -> Self::WithOption {
+                $name {
+                    $($before_field: self.$before_field,)*
+                    $field: value.into_expr(),
+                    $($after_field: self.$after_field,)*
+                }
+            }
+        }
+
+        impl_with_options!(
+            $name
+            [$($before_field: $before,)* $field: $param,]
+            [$($after_field: $after = $after_option where $after_bound,)*]
+        );
+    };
+}
 
 #[derive(Copy, Clone, Debug, Serialize, Default)]
 pub struct NoOptions {}
@@ -1310,3 +3936,182 @@ impl<
         }
     }
 }
+
+options! {
+    pub struct ChangesOptions {
+        squash: SquashT = SquashOption where IsBoolOrNumber,
+        include_initial: IncludeInitialT = IncludeInitialOption where IsBool,
+        include_states: IncludeStatesT = IncludeStatesOption where IsBool,
+        include_types: IncludeTypesT = IncludeTypesOption where IsBool,
+        changefeed_queue_size: ChangefeedQueueSizeT = ChangefeedQueueSizeOption where IsNumber,
+    }
+}
+
+options! {
+    pub struct OrderByOptions {
+        index: IndexT = IndexOption where IsIndexOrdering,
+    }
+}
+
+options! {
+    pub struct GroupOptions {
+        index: IndexT = IndexOption where IsString,
+        multi: MultiT = MultiOption where IsBool,
+    }
+}
+
+/// Options for `fold_emit`, which always sets `emit`. `AccumulatorT` is the type of the
+/// accumulator passed to `final_emit`.
+#[derive(Copy, Clone, Debug, Serialize)]
+pub struct FoldOptions<AccumulatorT, EmitT: OptionValue, FinalEmitT: OptionValue = ()> {
+    emit: EmitT,
+    #[serde(skip_serializing_if = "OptionValue::is_unset")] final_emit: FinalEmitT,
+    #[serde(skip)] _phantom: PhantomData<*const AccumulatorT>,
+}
+
+impl<AccumulatorT, EmitT: OptionValue, FinalEmitT: OptionValue> Options
+    for FoldOptions<AccumulatorT, EmitT, FinalEmitT> {
+    fn all_unset(&self) -> bool {
+        false
+    }
+}
+
+impl<AccumulatorT, EmitT, ReturnT, FunctionT> WithOption<FinalEmitOption, FunctionT>
+    for FoldOptions<AccumulatorT, EmitT, ()>
+where
+    EmitT: OptionValue,
+    ReturnT: IntoExpr,
+    ReturnT::Out: IsSequence,
+    FunctionT: FnOnce(Var<AccumulatorT>) -> ReturnT
+        + IntoFunctionExpr<(AccumulatorT,), ReturnT::Out>,
+{
+    type WithOption = FoldOptions<
+        AccumulatorT,
+        EmitT,
+        Expr<FunctionOut<(AccumulatorT,), ReturnT::Out>, FunctionT::FunctionAst>,
+    >;
+
+    fn with_option(self, value: FunctionT) -> Self::WithOption {
+        FoldOptions {
+            emit: self.emit,
+            final_emit: value.into_function_expr(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+options! {
+    pub struct EqJoinOptions {
+        index: IndexT = IndexOption where IsString,
+        ordered: OrderedT = OrderedOption where IsBool,
+    }
+}
+
+options! {
+    pub struct DuringOptions {
+        left_bound: LeftBoundT = LeftBoundOption where IsString,
+        right_bound: RightBoundT = RightBoundOption where IsString,
+    }
+}
+
+options! {
+    pub struct Iso8601Options {
+        default_timezone: DefaultTimezoneT = DefaultTimezoneOption where IsString,
+    }
+}
+
+options! {
+    pub struct IndexCreateOptions {
+        multi: MultiT = MultiOption where IsBool,
+        geo: GeoT = GeoOption where IsBool,
+    }
+}
+
+options! {
+    pub struct IndexRenameOptions {
+        overwrite: OverwriteT = OverwriteOption where IsBool,
+    }
+}
+
+options! {
+    pub struct GetNearestOptions {
+        index: IndexT = IndexOption where IsString,
+        max_results: MaxResultsT = MaxResultsOption where IsNumber,
+        max_dist: MaxDistT = MaxDistOption where IsNumber,
+        unit: UnitT = UnitOption where IsString,
+        geo_system: GeoSystemT = GeoSystemOption where IsString,
+    }
+}
+
+options! {
+    pub struct DistanceOptions {
+        geo_system: GeoSystemT = GeoSystemOption where IsString,
+        unit: UnitT = UnitOption where IsString,
+    }
+}
+
+options! {
+    pub struct CircleOptions {
+        num_vertices: NumVerticesT = NumVerticesOption where IsNumber,
+        geo_system: GeoSystemT = GeoSystemOption where IsString,
+        unit: UnitT = UnitOption where IsString,
+        fill: FillT = FillOption where IsBool,
+    }
+}
+
+options! {
+    pub struct RandomOptions {
+        float: FloatT = FloatOption where IsBool,
+    }
+}
+
+options! {
+    pub struct InsertOptions {
+        durability: DurabilityT = DurabilityOption where IsString,
+        return_changes: ReturnChangesT = ReturnChangesOption where IsBoolOrString,
+        ignore_write_hook: IgnoreWriteHookT = IgnoreWriteHookOption where IsBool,
+        conflict: ConflictT = ConflictOption where IsConflictResolver,
+    }
+}
+
+options! {
+    pub struct UpdateOptions {
+        durability: DurabilityT = DurabilityOption where IsString,
+        return_changes: ReturnChangesT = ReturnChangesOption where IsBoolOrString,
+        non_atomic: NonAtomicT = NonAtomicOption where IsBool,
+        ignore_write_hook: IgnoreWriteHookT = IgnoreWriteHookOption where IsBool,
+    }
+}
+
+options! {
+    pub struct DeleteOptions {
+        durability: DurabilityT = DurabilityOption where IsString,
+        return_changes: ReturnChangesT = ReturnChangesOption where IsBoolOrString,
+        ignore_write_hook: IgnoreWriteHookT = IgnoreWriteHookOption where IsBool,
+    }
+}
+
+options! {
+    pub struct WaitOptions {
+        wait_for: WaitForT = WaitForOption where IsString,
+        timeout: TimeoutT = TimeoutOption where IsNumber,
+    }
+}
+
+options! {
+    pub struct ReconfigureOptions {
+        shards: ShardsT = ShardsOption where IsNumber,
+        replicas: ReplicasT = ReplicasOption where IsReplicas,
+        primary_replica_tag: PrimaryReplicaTagT = PrimaryReplicaTagOption where IsString,
+        nonvoting_replica_tags: NonvotingReplicaTagsT = NonvotingReplicaTagsOption
+            where IsStringArray,
+        dry_run: DryRunT = DryRunOption where IsBool,
+    }
+}
+
+options! {
+    pub struct EmergencyRepairOptions {
+        emergency_repair: EmergencyRepairT = EmergencyRepairOption where IsString,
+        dry_run: DryRunT = DryRunOption where IsBool,
+    }
+}
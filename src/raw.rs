@@ -1,28 +1,81 @@
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
 use errors::{ErrorKind, Result};
 use failure::ResultExt;
+use scram::ScramClient;
 use serde::Serialize;
 use serde_json;
-use std::io::{BufReader, ErrorKind as IoErrorKind, Read, Write};
+use std::io::{BufRead, BufReader, ErrorKind as IoErrorKind, Read, Write};
 use std::net::{Shutdown, SocketAddr, TcpStream};
 use std::time::Duration;
+use transport::Transport;
+#[cfg(feature = "tls")]
+use transport::TlsOptions;
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Ord, PartialOrd)]
 pub struct Token(pub u64);
 
-pub struct RawConnection {
+/// Options used to open (and reset) a `RawConnection`. Built from an endpoint, connecting as the
+/// `admin` user with an empty password unless specified otherwise.
+#[derive(Clone)]
+pub struct ConnectOptions {
     endpoint: SocketAddr,
-    tcp: BufReader<TcpStream>,
+    user: String,
+    password: String,
+    #[cfg(feature = "tls")]
+    tls: Option<TlsOptions>,
+}
+
+impl ConnectOptions {
+    pub fn new(endpoint: SocketAddr) -> Self {
+        ConnectOptions {
+            endpoint,
+            user: DEFAULT_USER.to_owned(),
+            password: String::new(),
+            #[cfg(feature = "tls")]
+            tls: None,
+        }
+    }
+
+    /// The user to authenticate as, `admin` by default.
+    pub fn user<UserT: Into<String>>(mut self, user: UserT) -> Self {
+        self.user = user.into();
+        self
+    }
+
+    /// The password of the user, empty by default.
+    pub fn password<PasswordT: Into<String>>(mut self, password: PasswordT) -> Self {
+        self.password = password.into();
+        self
+    }
+
+    /// Connect over TLS rather than plain TCP.
+    #[cfg(feature = "tls")]
+    pub fn tls(mut self, tls: TlsOptions) -> Self {
+        self.tls = Some(tls);
+        self
+    }
+}
+
+impl From<SocketAddr> for ConnectOptions {
+    fn from(endpoint: SocketAddr) -> Self {
+        ConnectOptions::new(endpoint)
+    }
+}
+
+pub struct RawConnection {
+    connect_options: ConnectOptions,
+    stream: BufReader<Transport>,
     write_buffer: Vec<u8>,
     next_token: u64,
     options: GlobalOptions,
 }
 
 impl RawConnection {
-    pub fn connect(endpoint: SocketAddr) -> Result<Self> {
+    pub fn connect<OptionsT: Into<ConnectOptions>>(options: OptionsT) -> Result<Self> {
+        let connect_options = options.into();
         Ok(RawConnection {
-            endpoint,
-            tcp: handshake(&endpoint)?,
+            stream: handshake(&connect_options)?,
+            connect_options,
             write_buffer: Vec::with_capacity(4096),
             next_token: 1,
             options: GlobalOptions::default(),
@@ -30,7 +83,7 @@ impl RawConnection {
     }
 
     pub fn close(&self) -> Result<()> {
-        match self.tcp.get_ref().shutdown(Shutdown::Both) {
+        match self.stream.get_ref().tcp().shutdown(Shutdown::Both) {
             Err(ref error) if error.kind() == IoErrorKind::NotConnected => Ok(()),
             result @ _ => {
                 result.context(ErrorKind::Connection("failed to close socket".into()))?;
@@ -41,20 +94,36 @@ impl RawConnection {
 
     pub fn reset(&mut self) -> Result<()> {
         self.next_token = 1;
-        match handshake(&self.endpoint) {
-            Ok(tcp) => {
-                self.tcp = tcp;
+        match handshake(&self.connect_options) {
+            Ok(stream) => {
+                self.stream = stream;
                 Ok(())
             }
             Err(error) => {
-                let _ = self.tcp.get_mut().shutdown(Shutdown::Both);
+                let _ = self.stream.get_ref().tcp().shutdown(Shutdown::Both);
                 Err(error.into())
             }
         }
     }
 
+    /// Gives up the handshaken socket, along with the next unused token, so that it can be
+    /// driven by an `AsyncConnection`. Fails for TLS connections.
+    #[cfg(feature = "async")]
+    pub(crate) fn into_tcp(self) -> Result<(TcpStream, Token)> {
+        if !self.stream.buffer().is_empty() {
+            return Err(ErrorKind::Connection("unread data on connection".into()).into());
+        }
+        match self.stream.into_inner() {
+            Transport::Tcp(tcp) => Ok((tcp, Token(self.next_token))),
+            #[cfg(feature = "tls")]
+            Transport::Tls(_) => Err(ErrorKind::Connection(
+                "TLS connections cannot be used asynchronously".into(),
+            ).into()),
+        }
+    }
+
     pub fn is_open(&mut self) -> bool {
-        let tcp = self.tcp.get_mut();
+        let mut tcp = self.stream.get_ref().tcp();
         tcp.set_nonblocking(true)
             .and_then(|_| {
                 let is_ok = match tcp.read(&mut []) {
@@ -67,154 +136,363 @@ impl RawConnection {
     }
 
     pub fn start_request<QueryT: Serialize>(&mut self, query: QueryT) -> Result<Token> {
-        self.write_buffer.clear();
-        self.write_buffer.resize(REQUEST_HEADER_SIZE, 0u8);
-        BigEndian::write_u64(&mut self.write_buffer, self.next_token);
         let token = Token(self.next_token);
         self.next_token += 1;
+        encode_start_request(&mut self.write_buffer, token, query, &self.options)?;
+        self.send_start_request()?;
+        Ok(token)
+    }
+
+    /// Like `start_request`, but with the given global options instead of the default ones.
+    pub fn start_request_with<QueryT: Serialize>(
+        &mut self,
+        query: QueryT,
+        options: &GlobalOptions,
+    ) -> Result<Token> {
+        let token = Token(self.next_token);
+        self.next_token += 1;
+        encode_start_request(&mut self.write_buffer, token, query, options)?;
+        self.send_start_request()?;
+        Ok(token)
+    }
 
-        serde_json::to_writer(
-            &mut self.write_buffer,
-            &(::enums::query::START, query, &self.options),
-        ).context(ErrorKind::Connection("failed to serialize request".into()))?;
-        let request_size = self.write_buffer.len() - REQUEST_HEADER_SIZE;
-        assert!(
-            request_size < u32::max_value() as usize,
-            "Request too large."
-        );
-        LittleEndian::write_u32(
-            &mut self.write_buffer[REQUEST_LENGTH_OFFSET..REQUEST_HEADER_SIZE],
-            request_size as u32,
-        );
-        debug!(
-            "Sent request {:?}, size={:?}: {:?}",
-            token,
-            request_size,
-            String::from_utf8_lossy(&self.write_buffer[REQUEST_HEADER_SIZE..]),
-        );
-        self.tcp
+    fn send_start_request(&mut self) -> Result<()> {
+        self.stream
             .get_mut()
             .write_all(&self.write_buffer)
             .context(ErrorKind::Connection("failed to send start request".into()))?;
-        self.tcp.get_mut().flush().context(ErrorKind::Connection(
+        self.stream.get_mut().flush().context(ErrorKind::Connection(
             "failed to flush start request".into(),
         ))?;
-        Ok(token)
+        Ok(())
     }
 
     pub fn continue_request(&mut self, token: Token) -> Result<()> {
-        let mut request = CONTINUE_REQUEST_TEMPLATE;
-        BigEndian::write_u64(&mut request[..REQUEST_LENGTH_OFFSET], token.0);
-        self.tcp
+        let request = encode_continue_request(token);
+        self.stream
             .get_mut()
             .write_all(&request)
             .context(ErrorKind::Connection(
                 "failed to send continue request".into(),
             ))?;
-        self.tcp.get_mut().flush().context(ErrorKind::Connection(
+        self.stream.get_mut().flush().context(ErrorKind::Connection(
             "failed to flush continue request".into(),
         ))?;
         Ok(())
     }
 
+    pub fn stop_request(&mut self, token: Token) -> Result<()> {
+        let request = encode_stop_request(token);
+        self.stream
+            .get_mut()
+            .write_all(&request)
+            .context(ErrorKind::Connection("failed to send stop request".into()))?;
+        self.stream.get_mut().flush().context(ErrorKind::Connection(
+            "failed to flush stop request".into(),
+        ))?;
+        Ok(())
+    }
+
     pub fn recv<'a, F: FnOnce(Token) -> &'a mut Vec<u8>>(
         &mut self,
         wait: Wait,
         buffer: F,
     ) -> Result<Option<Token>> {
-        let mut header = [0u8; REQUEST_HEADER_SIZE];
-        let header_read_result = match wait {
-            Wait::Yes => self.tcp.read_exact(&mut header),
-            Wait::No => {
-                self.tcp
-                    .get_mut()
-                    .set_nonblocking(true)
-                    .context(ErrorKind::Connection("failed to set nonblocking".into()))?;
-                let result = self.tcp.read_exact(&mut header);
-                self.tcp
-                    .get_mut()
-                    .set_nonblocking(false)
-                    .context(ErrorKind::Connection("failed to unset nonblocking".into()))?;
-                result
-            }
-            Wait::For(duration) => {
-                self.tcp
-                    .get_mut()
-                    .set_read_timeout(Some(duration))
-                    .context(ErrorKind::Connection("failed to set read timeout".into()))?;
-                let result = self.tcp.read_exact(&mut header);
-                self.tcp
-                    .get_mut()
-                    .set_read_timeout(Some(Duration::from_millis(MESSAGE_TIMEOUT_MS)))
-                    .context(ErrorKind::Connection("failed to reset read timeout".into()))?;
-                result
+        // Only the wait for the first bytes of the header follows `wait`; once a response has
+        // started arriving the rest of it is read with the usual message timeout. `Wait::Yes`
+        // gives up with an error after the message timeout, `Wait::Forever` keeps waiting (a
+        // quiet changefeed may go arbitrarily long without sending anything) and the other modes
+        // report no response.
+        let mut header = [0u8; RESPONSE_HEADER_SIZE];
+        match wait {
+            Wait::Yes | Wait::Forever => {}
+            Wait::No => self
+                .stream
+                .get_ref()
+                .tcp()
+                .set_nonblocking(true)
+                .context(ErrorKind::Connection("failed to set nonblocking".into()))?,
+            Wait::For(duration) => self
+                .stream
+                .get_ref()
+                .tcp()
+                .set_read_timeout(Some(duration))
+                .context(ErrorKind::Connection("failed to set read timeout".into()))?,
+        }
+        let header_read_result = loop {
+            match self.stream.read(&mut header) {
+                Ok(0) => break Err(IoErrorKind::UnexpectedEof.into()),
+                Err(ref error) if error.kind() == IoErrorKind::Interrupted => {}
+                Err(ref error) if is_timeout(error) && wait == Wait::Forever => {}
+                result => break result,
             }
         };
-        debug!("Received header: {:?}", header);
-
-        match header_read_result {
-            Err(ref error) if error.kind() == IoErrorKind::TimedOut => {
-                return Ok(None);
-            }
-            result @ _ => result.context(ErrorKind::Connection("failed to read header".into()))?,
+        match wait {
+            Wait::Yes | Wait::Forever => {}
+            Wait::No => self
+                .stream
+                .get_ref()
+                .tcp()
+                .set_nonblocking(false)
+                .context(ErrorKind::Connection("failed to unset nonblocking".into()))?,
+            Wait::For(_) => self
+                .stream
+                .get_ref()
+                .tcp()
+                .set_read_timeout(Some(Duration::from_millis(MESSAGE_TIMEOUT_MS)))
+                .context(ErrorKind::Connection("failed to reset read timeout".into()))?,
         }
 
-        let (token, size) = header.split_at(REQUEST_LENGTH_OFFSET);
-        let token = Token(BigEndian::read_u64(token));
-        let size = LittleEndian::read_u32(size);
+        let header_read = match header_read_result {
+            Err(ref error) if is_timeout(error) && wait != Wait::Yes => return Ok(None),
+            result => result.context(ErrorKind::Connection("failed to read header".into()))?,
+        };
+        self.stream
+            .read_exact(&mut header[header_read..])
+            .context(ErrorKind::Connection("failed to read header".into()))?;
+        debug!("Received header: {:?}", header);
+
+        let (token, size) = decode_response_header(&header);
         debug!("Header: token={:?} size={}", token, size);
 
         let buffer = buffer(token);
         let initial_buffer_len = buffer.len();
-        let final_buffer_len = initial_buffer_len + 4 + size as usize;
+        let final_buffer_len = initial_buffer_len + 4 + size;
         buffer.resize(final_buffer_len, 0u8);
-        BigEndian::write_u32(&mut buffer[initial_buffer_len..], size);
-        self.tcp
+        BigEndian::write_u32(&mut buffer[initial_buffer_len..], size as u32);
+        self.stream
             .read_exact(&mut buffer[initial_buffer_len + 4..])
             .context(ErrorKind::Connection("failed to read response body".into()))?;
         Ok(Some(token))
     }
 }
 
-fn handshake(endpoint: &SocketAddr) -> Result<BufReader<TcpStream>> {
-    let mut tcp =
-        TcpStream::connect_timeout(&endpoint, Duration::from_millis(CONNECTION_TIMEOUT_MS))
-            .context(ErrorKind::Connection("connection error".into()))?;
+/// Serializes a START query with its header into `buffer`, replacing any previous contents.
+pub(crate) fn encode_start_request<QueryT: Serialize>(
+    buffer: &mut Vec<u8>,
+    token: Token,
+    query: QueryT,
+    options: &GlobalOptions,
+) -> Result<()> {
+    buffer.clear();
+    buffer.resize(REQUEST_HEADER_SIZE, 0u8);
+    BigEndian::write_u64(buffer, token.0);
+
+    serde_json::to_writer(&mut *buffer, &(::enums::query::START, query, options))
+        .context(ErrorKind::Connection("failed to serialize request".into()))?;
+    let request_size = buffer.len() - REQUEST_HEADER_SIZE;
+    assert!(
+        request_size < u32::max_value() as usize,
+        "Request too large."
+    );
+    LittleEndian::write_u32(
+        &mut buffer[REQUEST_LENGTH_OFFSET..REQUEST_HEADER_SIZE],
+        request_size as u32,
+    );
+    debug!(
+        "Sent request {:?}, size={:?}: {:?}",
+        token,
+        request_size,
+        String::from_utf8_lossy(&buffer[REQUEST_HEADER_SIZE..]),
+    );
+    Ok(())
+}
+
+pub(crate) fn encode_continue_request(token: Token) -> [u8; 15] {
+    let mut request = CONTINUE_REQUEST_TEMPLATE;
+    BigEndian::write_u64(&mut request[..REQUEST_LENGTH_OFFSET], token.0);
+    request
+}
+
+pub(crate) fn encode_stop_request(token: Token) -> [u8; 15] {
+    let mut request = STOP_REQUEST_TEMPLATE;
+    BigEndian::write_u64(&mut request[..REQUEST_LENGTH_OFFSET], token.0);
+    request
+}
+
+/// Splits a response header into the token it answers and the size of the body that follows.
+pub(crate) fn decode_response_header(header: &[u8; RESPONSE_HEADER_SIZE]) -> (Token, usize) {
+    let (token, size) = header.split_at(REQUEST_LENGTH_OFFSET);
+    (
+        Token(BigEndian::read_u64(token)),
+        LittleEndian::read_u32(size) as usize,
+    )
+}
+
+fn handshake(options: &ConnectOptions) -> Result<BufReader<Transport>> {
+    let tcp = TcpStream::connect_timeout(
+        &options.endpoint,
+        Duration::from_millis(CONNECTION_TIMEOUT_MS),
+    ).context(ErrorKind::Connection("connection error".into()))?;
     tcp.set_read_timeout(Some(Duration::from_millis(MESSAGE_TIMEOUT_MS)))
         .context(ErrorKind::Connection("set read timeout error".into()))?;
     tcp.set_write_timeout(Some(Duration::from_millis(MESSAGE_TIMEOUT_MS)))
         .context(ErrorKind::Connection("set write timeout error".into()))?;
     tcp.set_nodelay(true)
         .context(ErrorKind::Connection("set nodelay error".into()))?;
-    tcp.write_all(HANDSHAKE_REQUEST)
+
+    #[cfg(feature = "tls")]
+    let transport = match options.tls {
+        Some(ref tls) => Transport::tls(tcp, tls)?,
+        None => Transport::Tcp(tcp),
+    };
+    #[cfg(not(feature = "tls"))]
+    let transport = Transport::Tcp(tcp);
+
+    let mut stream = BufReader::new(transport);
+    stream
+        .get_mut()
+        .write_all(HANDSHAKE_MAGIC_V1_0)
         .context(ErrorKind::Connection("error sending handshake".into()))?;
-    tcp.flush()
+    stream
+        .get_mut()
+        .flush()
         .context(ErrorKind::Connection("error flushing handshake".into()))?;
+    let server_info = read_handshake_message(&mut stream)?;
+    server_info.check()?;
+    if server_info.min_protocol_version.unwrap_or(0) > HANDSHAKE_PROTOCOL_VERSION
+        || server_info.max_protocol_version.unwrap_or(0) < HANDSHAKE_PROTOCOL_VERSION
+    {
+        return Err(ErrorKind::Connection(
+            format!(
+                "unsupported protocol version, server supports {:?}..={:?}",
+                server_info.min_protocol_version, server_info.max_protocol_version
+            ).into(),
+        ).into());
+    }
+
+    let mut scram = ScramClient::new(&options.user, &options.password);
+    write_handshake_message(
+        stream.get_mut(),
+        &ClientFirstMessage {
+            protocol_version: HANDSHAKE_PROTOCOL_VERSION,
+            authentication_method: HANDSHAKE_AUTHENTICATION_METHOD,
+            authentication: scram.client_first(),
+        },
+    )?;
+    let server_first = read_handshake_message(&mut stream)?.authentication()?;
+    write_handshake_message(
+        stream.get_mut(),
+        &ClientFinalMessage {
+            authentication: scram.client_final(&server_first)?,
+        },
+    )?;
+    let server_final = read_handshake_message(&mut stream)?.authentication()?;
+    scram.verify_server_final(&server_final)?;
+
+    Ok(stream)
+}
 
-    let mut tcp = BufReader::new(tcp);
-    let mut response = [0; HANDSHAKE_RESPONSE_LEN];
-    tcp.read_exact(&mut response)
+/// Reads a null-terminated JSON handshake message. Servers which do not support the V1_0
+/// handshake reply with a plain error string instead.
+fn read_handshake_message<ReadT: BufRead>(read: &mut ReadT) -> Result<HandshakeResponse> {
+    let mut message = Vec::with_capacity(256);
+    read.read_until(0, &mut message)
         .context(ErrorKind::Connection(
             "error reading handshake response".into(),
         ))?;
+    if message.pop() != Some(0) {
+        return Err(ErrorKind::Connection("handshake response not terminated".into()).into());
+    }
+    debug!("Handshake response: {}", String::from_utf8_lossy(&message));
+    serde_json::from_slice(&message).map_err(|_| {
+        ErrorKind::Connection(
+            format!(
+                "handshake failed, response: {}",
+                String::from_utf8_lossy(&message)
+            ).into(),
+        ).into()
+    })
+}
 
-    if response == HANDSHAKE_SUCCESS {
-        Ok(tcp)
-    } else {
-        Err(
-            ErrorKind::Connection(format!("handshake failed, response: {:?}", response).into())
-                .into(),
-        )
+fn write_handshake_message<MessageT: Serialize, WriteT: Write>(
+    write: &mut WriteT,
+    message: &MessageT,
+) -> Result<()> {
+    let mut buffer = serde_json::to_vec(message)
+        .context(ErrorKind::Connection("failed to serialize handshake".into()))?;
+    buffer.push(0);
+    write
+        .write_all(&buffer)
+        .context(ErrorKind::Connection("error sending handshake".into()))?;
+    write
+        .flush()
+        .context(ErrorKind::Connection("error flushing handshake".into()))?;
+    Ok(())
+}
+
+#[derive(Serialize)]
+struct ClientFirstMessage {
+    protocol_version: u32,
+    authentication_method: &'static str,
+    authentication: String,
+}
+
+#[derive(Serialize)]
+struct ClientFinalMessage {
+    authentication: String,
+}
+
+#[derive(Deserialize)]
+struct HandshakeResponse {
+    success: bool,
+    #[serde(default)]
+    authentication: Option<String>,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    error_code: Option<i32>,
+    #[serde(default)]
+    min_protocol_version: Option<u32>,
+    #[serde(default)]
+    max_protocol_version: Option<u32>,
+}
+
+impl HandshakeResponse {
+    fn check(&self) -> Result<()> {
+        if self.success {
+            return Ok(());
+        }
+        let error = self
+            .error
+            .clone()
+            .unwrap_or_else(|| "unknown handshake error".to_owned());
+        Err(match self.error_code {
+            Some(code) if code >= 10 && code <= 20 => ErrorKind::Authentication(error.into()),
+            _ => ErrorKind::Connection(error.into()),
+        }.into())
+    }
+
+    fn authentication(self) -> Result<String> {
+        self.check()?;
+        self.authentication.ok_or_else(|| {
+            ErrorKind::Connection("authentication missing in handshake response".into()).into()
+        })
     }
 }
 
-#[derive(Serialize, Default)]
+/// Options sent with a query, which apply to the whole query rather than a single term.
+#[derive(Clone, Debug, Serialize, Default)]
 pub struct GlobalOptions {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub read_mode: Option<ReadMode>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binary_format: Option<BinaryFormat>,
 }
 
-#[derive(Serialize)]
+impl GlobalOptions {
+    pub fn read_mode(mut self, read_mode: ReadMode) -> Self {
+        self.read_mode = Some(read_mode);
+        self
+    }
+
+    pub fn binary_format(mut self, binary_format: BinaryFormat) -> Self {
+        self.binary_format = Some(binary_format);
+        self
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
 pub enum ReadMode {
     #[serde(rename = "single")]
     Single,
@@ -224,22 +502,48 @@ pub enum ReadMode {
     Outdated,
 }
 
+/// How binary values are returned. The server sends them as the `BINARY` pseudo-type in either
+/// format, and other drivers convert them to native bytes under `Native`. This driver never
+/// converts results, so read binary values as `types::ReqlBinary` (not `Vec<u8>`) whichever
+/// format is set.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize)]
+pub enum BinaryFormat {
+    #[serde(rename = "native")]
+    Native,
+    #[serde(rename = "raw")]
+    Raw,
+}
+
+/// How long to wait for a response before giving up.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub enum Wait {
+    /// Until the message timeout, after which reading fails with a connection error.
     Yes,
+    /// Don't wait: only read a response which has already arrived.
     No,
+    /// For at most the given duration, then report that nothing arrived.
     For(Duration),
+    /// Indefinitely, for changefeeds which may go quiet for longer than the message timeout.
+    Forever,
+}
+
+/// Whether a read failed only because nothing arrived in time: a read timeout is reported as
+/// `WouldBlock` on Unix and `TimedOut` on Windows, and a non-blocking read as `WouldBlock`.
+fn is_timeout(error: &::std::io::Error) -> bool {
+    let kind = error.kind();
+    kind == IoErrorKind::WouldBlock || kind == IoErrorKind::TimedOut
 }
 
 const CONTINUE_REQUEST_TEMPLATE: [u8; 15] = [0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, b'[', b'2', b']'];
+const STOP_REQUEST_TEMPLATE: [u8; 15] = [0, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, b'[', b'3', b']'];
 const REQUEST_HEADER_SIZE: usize = 8 + 4;
+pub(crate) const RESPONSE_HEADER_SIZE: usize = REQUEST_HEADER_SIZE;
 const REQUEST_LENGTH_OFFSET: usize = 8;
 
 const CONNECTION_TIMEOUT_MS: u64 = 5000;
 const MESSAGE_TIMEOUT_MS: u64 = 30000;
 
-const HANDSHAKE_REQUEST: &[u8] = &[
-    0x20, 0x2d, 0x0c, 0x40, 0x00, 0x00, 0x00, 0x00, 0xc7, 0x70, 0x69, 0x7e,
-];
-const HANDSHAKE_RESPONSE_LEN: usize = 8;
-const HANDSHAKE_SUCCESS: [u8; HANDSHAKE_RESPONSE_LEN] =
-    [0x53, 0x55, 0x43, 0x43, 0x45, 0x53, 0x53, 0x00];
+const DEFAULT_USER: &str = "admin";
+const HANDSHAKE_MAGIC_V1_0: &[u8] = &[0xc3, 0xbd, 0xc2, 0x34];
+const HANDSHAKE_PROTOCOL_VERSION: u32 = 0;
+const HANDSHAKE_AUTHENTICATION_METHOD: &str = "SCRAM-SHA-256";
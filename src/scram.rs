@@ -0,0 +1,180 @@
+//! Client side of the SCRAM-SHA-256 exchange (RFC 5802, RFC 7677) used by the V1_0 handshake.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use errors::{ErrorKind, Result};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use rand::{self, Rng};
+use sha2::{Digest, Sha256};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub struct ScramClient {
+    password: String,
+    client_first_bare: String,
+    client_nonce: String,
+    server_signature: Option<Vec<u8>>,
+}
+
+impl ScramClient {
+    pub fn new(user: &str, password: &str) -> Self {
+        let nonce: [u8; NONCE_LEN] = rand::thread_rng().gen();
+        ScramClient::with_nonce(user, password, BASE64.encode(&nonce))
+    }
+
+    fn with_nonce(user: &str, password: &str, client_nonce: String) -> Self {
+        let user = user.replace('=', "=3D").replace(',', "=2C");
+        ScramClient {
+            password: password.to_owned(),
+            client_first_bare: format!("n={},r={}", user, client_nonce),
+            client_nonce,
+            server_signature: None,
+        }
+    }
+
+    /// The `client-first-message`, sent with the handshake's first JSON message.
+    pub fn client_first(&self) -> String {
+        format!("{}{}", GS2_HEADER, self.client_first_bare)
+    }
+
+    /// Consumes the `server-first-message` and produces the `client-final-message` carrying the
+    /// client proof.
+    pub fn client_final(&mut self, server_first: &str) -> Result<String> {
+        let mut nonce = None;
+        let mut salt = None;
+        let mut iterations = None;
+        for attribute in server_first.split(',') {
+            if attribute.starts_with("r=") {
+                nonce = Some(&attribute[2..]);
+            } else if attribute.starts_with("s=") {
+                salt = Some(BASE64.decode(&attribute[2..]).map_err(|_| invalid("salt"))?);
+            } else if attribute.starts_with("i=") {
+                iterations = Some(
+                    attribute[2..]
+                        .parse::<u32>()
+                        .map_err(|_| invalid("iterations"))?,
+                );
+            }
+        }
+        let nonce = nonce.ok_or_else(|| invalid("nonce"))?;
+        let salt = salt.ok_or_else(|| invalid("salt"))?;
+        let iterations = iterations.ok_or_else(|| invalid("iterations"))?;
+        if !nonce.starts_with(&self.client_nonce) {
+            return Err(ErrorKind::Authentication(
+                "server nonce does not extend the client nonce".into(),
+            ).into());
+        }
+
+        let client_final_without_proof = format!("c={},r={}", BASE64.encode(GS2_HEADER), nonce);
+        let auth_message = format!(
+            "{},{},{}",
+            self.client_first_bare, server_first, client_final_without_proof
+        );
+
+        let mut salted_password = [0u8; 32];
+        pbkdf2_hmac::<Sha256>(
+            self.password.as_bytes(),
+            &salt,
+            iterations,
+            &mut salted_password,
+        );
+        let client_key = hmac(&salted_password, b"Client Key");
+        let stored_key = Sha256::digest(&client_key);
+        let client_signature = hmac(&stored_key, auth_message.as_bytes());
+        let proof: Vec<u8> = client_key
+            .iter()
+            .zip(client_signature.iter())
+            .map(|(key, signature)| key ^ signature)
+            .collect();
+
+        let server_key = hmac(&salted_password, b"Server Key");
+        self.server_signature = Some(hmac(&server_key, auth_message.as_bytes()));
+
+        Ok(format!("{},p={}", client_final_without_proof, BASE64.encode(&proof)))
+    }
+
+    /// Checks the server signature in the `server-final-message`, proving the server knows the
+    /// password too.
+    pub fn verify_server_final(&self, server_final: &str) -> Result<()> {
+        let expected = self
+            .server_signature
+            .as_ref()
+            .expect("verify_server_final called before client_final");
+        let signature = server_final
+            .split(',')
+            .find(|attribute| attribute.starts_with("v="))
+            .ok_or_else(|| invalid("server signature"))?;
+        let signature = BASE64
+            .decode(&signature[2..])
+            .map_err(|_| invalid("server signature"))?;
+        if &signature == expected {
+            Ok(())
+        } else {
+            Err(ErrorKind::Authentication("invalid server signature".into()).into())
+        }
+    }
+}
+
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = HmacSha256::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn invalid(what: &'static str) -> ErrorKind {
+    ErrorKind::Authentication(format!("invalid {} in server message", what).into())
+}
+
+const GS2_HEADER: &str = "n,,";
+const NONCE_LEN: usize = 18;
+
+#[cfg(test)]
+mod tests {
+    use super::ScramClient;
+    use errors::ErrorKind;
+
+    // The SCRAM-SHA-256 example exchange from RFC 7677, section 3.
+    const CLIENT_NONCE: &str = "rOprNGfwEbeRWgbNEkqO";
+    const CLIENT_FIRST: &str = "n,,n=user,r=rOprNGfwEbeRWgbNEkqO";
+    const SERVER_FIRST: &str = "r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+                                s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096";
+    const CLIENT_FINAL: &str = "c=biws,r=rOprNGfwEbeRWgbNEkqO%hvYDpWUa2RaTCAfuxFIlj)hNlF$k0,\
+                                p=dHzbZapWIk4jUhN+Ute9ytag9zjfMHgsqmmiz7AndVQ=";
+    const SERVER_FINAL: &str = "v=6rriTRBi23WpRR/wtup+mMhUZUn/dB5nLTJRsjl95G4=";
+
+    fn client() -> ScramClient {
+        ScramClient::with_nonce("user", "pencil", CLIENT_NONCE.to_owned())
+    }
+
+    #[test]
+    fn rfc_7677_exchange() {
+        let mut client = client();
+        assert_eq!(client.client_first(), CLIENT_FIRST);
+        assert_eq!(client.client_final(SERVER_FIRST).unwrap(), CLIENT_FINAL);
+        client.verify_server_final(SERVER_FINAL).unwrap();
+    }
+
+    #[test]
+    fn server_signature_mismatch_is_an_error() {
+        let mut client = client();
+        client.client_final(SERVER_FIRST).unwrap();
+        let error = client
+            .verify_server_final("v=AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=")
+            .unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &ErrorKind::Authentication("invalid server signature".into())
+        );
+    }
+
+    #[test]
+    fn server_nonce_must_extend_client_nonce() {
+        let mut client = client();
+        assert!(
+            client
+                .client_final("r=someone-else,s=W22ZaJ0SNY7soEsUEjb6gQ==,i=4096")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,140 @@
+//! The byte stream under a `RawConnection`: plain TCP or, with the `tls` feature, TLS over TCP.
+
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+
+#[cfg(feature = "tls")]
+use errors::{ErrorKind, Result};
+#[cfg(feature = "tls")]
+use failure::ResultExt;
+#[cfg(feature = "tls")]
+use native_tls::{Certificate, Identity, TlsConnector, TlsStream};
+
+pub enum Transport {
+    Tcp(TcpStream),
+    #[cfg(feature = "tls")]
+    Tls(Box<TlsStream<TcpStream>>),
+}
+
+impl Transport {
+    /// The underlying TCP socket, used for timeouts, non-blocking reads and shutdown.
+    pub fn tcp(&self) -> &TcpStream {
+        match *self {
+            Transport::Tcp(ref tcp) => tcp,
+            #[cfg(feature = "tls")]
+            Transport::Tls(ref tls) => tls.get_ref(),
+        }
+    }
+
+    #[cfg(feature = "tls")]
+    pub fn tls(tcp: TcpStream, options: &TlsOptions) -> Result<Self> {
+        let mut builder = TlsConnector::builder();
+        for certificate in &options.ca_certificates {
+            builder.add_root_certificate(
+                Certificate::from_pem(certificate)
+                    .context(ErrorKind::Connection("invalid CA certificate".into()))?,
+            );
+        }
+        if let Some((ref certificate, ref key)) = options.identity {
+            builder.identity(
+                Identity::from_pkcs8(certificate, key)
+                    .context(ErrorKind::Connection("invalid client certificate".into()))?,
+            );
+        }
+        builder.danger_accept_invalid_hostnames(!options.verify_hostname);
+        let connector = builder
+            .build()
+            .context(ErrorKind::Connection("invalid TLS configuration".into()))?;
+        let tls = connector
+            .connect(&options.domain, tcp)
+            .map_err(|error| ErrorKind::Tls(error.to_string().into()))?;
+        Ok(Transport::Tls(Box::new(tls)))
+    }
+}
+
+impl Read for Transport {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        match *self {
+            Transport::Tcp(ref mut tcp) => tcp.read(buffer),
+            #[cfg(feature = "tls")]
+            Transport::Tls(ref mut tls) => tls.read(buffer),
+        }
+    }
+}
+
+impl Write for Transport {
+    fn write(&mut self, buffer: &[u8]) -> io::Result<usize> {
+        match *self {
+            Transport::Tcp(ref mut tcp) => tcp.write(buffer),
+            #[cfg(feature = "tls")]
+            Transport::Tls(ref mut tls) => tls.write(buffer),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match *self {
+            Transport::Tcp(ref mut tcp) => tcp.flush(),
+            #[cfg(feature = "tls")]
+            Transport::Tls(ref mut tls) => tls.flush(),
+        }
+    }
+}
+
+/// TLS settings for `ConnectOptions::tls`. The server certificate is checked against the system
+/// roots plus any given CA certificates, and its hostname against `domain`.
+#[cfg(feature = "tls")]
+#[derive(Clone)]
+pub struct TlsOptions {
+    domain: String,
+    ca_certificates: Vec<Vec<u8>>,
+    identity: Option<(Vec<u8>, Vec<u8>)>,
+    verify_hostname: bool,
+}
+
+#[cfg(feature = "tls")]
+impl TlsOptions {
+    /// `domain` is sent for SNI and is the name the server certificate must be valid for.
+    pub fn new<DomainT: Into<String>>(domain: DomainT) -> Self {
+        TlsOptions {
+            domain: domain.into(),
+            ca_certificates: Vec::new(),
+            identity: None,
+            verify_hostname: true,
+        }
+    }
+
+    /// Trust the certificates in a PEM CA bundle, in addition to the system roots.
+    pub fn ca_bundle(mut self, pem: &[u8]) -> Self {
+        self.ca_certificates.extend(split_pem(pem));
+        self
+    }
+
+    /// Present a client certificate: a PEM certificate chain and its PEM (PKCS#8) private key.
+    pub fn client_certificate(mut self, certificate_pem: &[u8], key_pem: &[u8]) -> Self {
+        self.identity = Some((certificate_pem.to_owned(), key_pem.to_owned()));
+        self
+    }
+
+    /// Whether to check that the server certificate is valid for `domain`, true by default.
+    pub fn verify_hostname(mut self, verify: bool) -> Self {
+        self.verify_hostname = verify;
+        self
+    }
+}
+
+/// Splits a PEM bundle into its individual certificates.
+#[cfg(feature = "tls")]
+fn split_pem(pem: &[u8]) -> Vec<Vec<u8>> {
+    const END: &[u8] = b"-----END CERTIFICATE-----";
+    let mut certificates = Vec::new();
+    let mut start = 0;
+    while let Some(position) = pem[start..]
+        .windows(END.len())
+        .position(|window| window == END)
+    {
+        let end = start + position + END.len();
+        certificates.push(pem[start..end].to_owned());
+        start = end;
+    }
+    certificates
+}
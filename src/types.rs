@@ -0,0 +1,602 @@
+//! Types to deserialize the results of queries into, for the shapes ReQL itself defines.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+#[cfg(feature = "chrono")]
+use chrono::{DateTime, FixedOffset, Offset, TimeZone};
+use serde::de::{Deserialize, Deserializer, Error as DeserializeError};
+use serde::ser::{Serialize, Serializer};
+use std::collections::{BTreeMap, HashMap};
+use std::hash::Hash;
+use std::ops::{Deref, DerefMut};
+use std::slice;
+use std::vec;
+
+/// One event from a changefeed started with `changes`. A new document has no `old_val`, a deleted
+/// one no `new_val`; with `include_states` set, state notes carry only `state`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Change<DocumentT> {
+    pub old_val: Option<DocumentT>,
+    pub new_val: Option<DocumentT>,
+    /// Only sent with the `include_types` option.
+    #[serde(rename = "type")]
+    pub change_type: Option<ChangeType>,
+    /// Only sent with the `include_states` option.
+    pub state: Option<FeedState>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeType {
+    Add,
+    Remove,
+    Change,
+    Initial,
+    Uninitial,
+    State,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedState {
+    Initializing,
+    Ready,
+}
+
+/// One pair from `inner_join`, `outer_join` or `eq_join`. Use `Join<LeftT, Option<RightT>>` for
+/// `outer_join`, where unmatched elements have no `right`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Join<LeftT, RightT> {
+    pub left: LeftT,
+    pub right: RightT,
+}
+
+/// The result of a grouped query (`group` followed by an optional aggregation), returned as the
+/// `GROUPED_DATA` pseudo-type. Groups keep the order the server sent them in; grouping values are
+/// arbitrary JSON, so converting into a `HashMap` or `BTreeMap` is left to the caller.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GroupedData<KeyT, ValueT> {
+    groups: Vec<(KeyT, ValueT)>,
+}
+
+impl<KeyT, ValueT> GroupedData<KeyT, ValueT> {
+    pub fn len(&self) -> usize {
+        self.groups.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.groups.is_empty()
+    }
+
+    /// The value of the group with the given grouping value, if any.
+    pub fn get(&self, key: &KeyT) -> Option<&ValueT>
+    where
+        KeyT: PartialEq,
+    {
+        self.groups
+            .iter()
+            .find(|&&(ref group, _)| group == key)
+            .map(|&(_, ref value)| value)
+    }
+
+    pub fn iter<'a>(&'a self) -> slice::Iter<'a, (KeyT, ValueT)> {
+        self.groups.iter()
+    }
+
+    pub fn into_vec(self) -> Vec<(KeyT, ValueT)> {
+        self.groups
+    }
+}
+
+impl<KeyT, ValueT> IntoIterator for GroupedData<KeyT, ValueT> {
+    type Item = (KeyT, ValueT);
+    type IntoIter = vec::IntoIter<(KeyT, ValueT)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.groups.into_iter()
+    }
+}
+
+impl<'a, KeyT, ValueT> IntoIterator for &'a GroupedData<KeyT, ValueT> {
+    type Item = &'a (KeyT, ValueT);
+    type IntoIter = slice::Iter<'a, (KeyT, ValueT)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.groups.iter()
+    }
+}
+
+impl<KeyT: Eq + Hash, ValueT> From<GroupedData<KeyT, ValueT>> for HashMap<KeyT, ValueT> {
+    fn from(grouped: GroupedData<KeyT, ValueT>) -> Self {
+        grouped.groups.into_iter().collect()
+    }
+}
+
+impl<KeyT: Ord, ValueT> From<GroupedData<KeyT, ValueT>> for BTreeMap<KeyT, ValueT> {
+    fn from(grouped: GroupedData<KeyT, ValueT>) -> Self {
+        grouped.groups.into_iter().collect()
+    }
+}
+
+impl<'de, KeyT, ValueT> Deserialize<'de> for GroupedData<KeyT, ValueT>
+where
+    KeyT: Deserialize<'de>,
+    ValueT: Deserialize<'de>,
+{
+    fn deserialize<DeserializerT: Deserializer<'de>>(
+        deserializer: DeserializerT,
+    ) -> Result<Self, DeserializerT::Error> {
+        #[derive(Deserialize)]
+        struct Raw<KeyT, ValueT> {
+            #[serde(rename = "$reql_type$")]
+            reql_type: String,
+            data: Vec<(KeyT, ValueT)>,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        if raw.reql_type != GROUPED_DATA {
+            return Err(DeserializerT::Error::custom(format!(
+                "expected {} pseudo-type, got {}",
+                GROUPED_DATA, raw.reql_type
+            )));
+        }
+        Ok(GroupedData { groups: raw.data })
+    }
+}
+
+/// A point in time with a timezone, the `TIME` pseudo-type. Can be used in queries (as a
+/// `TimeOut`) and in documents, and read back from query results. With the `chrono` feature it
+/// converts to and from `chrono::DateTime`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReqlTime {
+    /// Seconds since the UNIX epoch; the server keeps millisecond precision.
+    pub epoch_time: f64,
+    /// The timezone as `"[+-]HH:MM"`.
+    pub timezone: String,
+}
+
+impl ReqlTime {
+    pub fn new<TimezoneT: Into<String>>(epoch_time: f64, timezone: TimezoneT) -> Self {
+        ReqlTime {
+            epoch_time,
+            timezone: timezone.into(),
+        }
+    }
+
+    pub fn utc(epoch_time: f64) -> Self {
+        ReqlTime::new(epoch_time, "+00:00")
+    }
+
+    /// The time as a `chrono::DateTime` in its own timezone, or `None` if it is out of chrono's
+    /// range or the timezone is malformed.
+    #[cfg(feature = "chrono")]
+    pub fn to_date_time(&self) -> Option<DateTime<FixedOffset>> {
+        let offset = parse_timezone(&self.timezone)?;
+        let millis = (self.epoch_time * 1000.0).round() as i64;
+        DateTime::from_timestamp_millis(millis).map(|time| time.with_timezone(&offset))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<TimezoneT: TimeZone> From<DateTime<TimezoneT>> for ReqlTime {
+    fn from(time: DateTime<TimezoneT>) -> Self {
+        let offset = time.offset().fix().local_minus_utc();
+        let (sign, offset) = if offset < 0 { ('-', -offset) } else { ('+', offset) };
+        ReqlTime {
+            epoch_time: time.timestamp() as f64 + f64::from(time.timestamp_subsec_millis()) / 1000.0,
+            timezone: format!("{}{:02}:{:02}", sign, offset / 3600, offset / 60 % 60),
+        }
+    }
+}
+
+#[cfg(feature = "chrono")]
+fn parse_timezone(timezone: &str) -> Option<FixedOffset> {
+    if timezone == "Z" {
+        return FixedOffset::east_opt(0);
+    }
+    let sign = match timezone.get(..1)? {
+        "+" => 1,
+        "-" => -1,
+        _ => return None,
+    };
+    let mut parts = timezone[1..].splitn(2, ':');
+    let hours: i32 = parts.next()?.parse().ok()?;
+    let minutes: i32 = parts.next().unwrap_or("0").parse().ok()?;
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+impl Serialize for ReqlTime {
+    fn serialize<SerializerT: Serializer>(
+        &self,
+        serializer: SerializerT,
+    ) -> Result<SerializerT::Ok, SerializerT::Error> {
+        #[derive(Serialize)]
+        struct Raw<'a> {
+            #[serde(rename = "$reql_type$")]
+            reql_type: &'static str,
+            epoch_time: f64,
+            timezone: &'a str,
+        }
+
+        Raw {
+            reql_type: TIME,
+            epoch_time: self.epoch_time,
+            timezone: &self.timezone,
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ReqlTime {
+    fn deserialize<DeserializerT: Deserializer<'de>>(
+        deserializer: DeserializerT,
+    ) -> Result<Self, DeserializerT::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            #[serde(rename = "$reql_type$")]
+            reql_type: String,
+            epoch_time: f64,
+            timezone: String,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        if raw.reql_type != TIME {
+            return Err(DeserializerT::Error::custom(format!(
+                "expected {} pseudo-type, got {}",
+                TIME, raw.reql_type
+            )));
+        }
+        Ok(ReqlTime {
+            epoch_time: raw.epoch_time,
+            timezone: raw.timezone,
+        })
+    }
+}
+
+/// A longitude/latitude pair, in degrees. Serialized as a GeoJSON position, `[longitude,
+/// latitude]`.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Point(pub f64, pub f64);
+
+impl Point {
+    pub fn longitude(&self) -> f64 {
+        self.0
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.1
+    }
+}
+
+/// A geometry object, the `GEOMETRY` pseudo-type: a GeoJSON `Point`, `LineString` or `Polygon`
+/// (the only types RethinkDB supports). Can be used in queries (as a `GeometryOut`) and in
+/// documents, and read back from query results.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Geometry {
+    Point(Point),
+    LineString(Vec<Point>),
+    /// The outer ring, followed by any holes. Each ring ends with its first point.
+    Polygon(Vec<Vec<Point>>),
+}
+
+/// The GeoJSON representation of a `Geometry`, owning its coordinates when deserializing and
+/// borrowing them when serializing.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "coordinates")]
+enum RawGeometry<PointT, LineT, PolygonT> {
+    Point(PointT),
+    LineString(LineT),
+    Polygon(PolygonT),
+}
+
+impl Serialize for Geometry {
+    fn serialize<SerializerT: Serializer>(
+        &self,
+        serializer: SerializerT,
+    ) -> Result<SerializerT::Ok, SerializerT::Error> {
+        #[derive(Serialize)]
+        struct Raw<'a> {
+            #[serde(rename = "$reql_type$")]
+            reql_type: &'static str,
+            #[serde(flatten)]
+            geometry: RawGeometry<&'a Point, &'a [Point], &'a [Vec<Point>]>,
+        }
+
+        Raw {
+            reql_type: GEOMETRY,
+            geometry: match *self {
+                Geometry::Point(ref point) => RawGeometry::Point(point),
+                Geometry::LineString(ref points) => RawGeometry::LineString(points),
+                Geometry::Polygon(ref rings) => RawGeometry::Polygon(rings),
+            },
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Geometry {
+    fn deserialize<DeserializerT: Deserializer<'de>>(
+        deserializer: DeserializerT,
+    ) -> Result<Self, DeserializerT::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            #[serde(rename = "$reql_type$")]
+            reql_type: String,
+            #[serde(flatten)]
+            geometry: RawGeometry<Point, Vec<Point>, Vec<Vec<Point>>>,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        if raw.reql_type != GEOMETRY {
+            return Err(DeserializerT::Error::custom(format!(
+                "expected {} pseudo-type, got {}",
+                GEOMETRY, raw.reql_type
+            )));
+        }
+        Ok(match raw.geometry {
+            RawGeometry::Point(point) => Geometry::Point(point),
+            RawGeometry::LineString(points) => Geometry::LineString(points),
+            RawGeometry::Polygon(rings) => Geometry::Polygon(rings),
+        })
+    }
+}
+
+/// The result of a write query such as `insert`, `update` or `delete`. `changes` is only sent
+/// with the `return_changes` option.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WriteResult<DocumentT> {
+    #[serde(default)]
+    pub inserted: u64,
+    #[serde(default)]
+    pub replaced: u64,
+    #[serde(default)]
+    pub unchanged: u64,
+    #[serde(default)]
+    pub skipped: u64,
+    #[serde(default)]
+    pub deleted: u64,
+    #[serde(default)]
+    pub errors: u64,
+    pub first_error: Option<String>,
+    /// The primary keys generated for inserted documents which didn't have one.
+    #[serde(default)]
+    pub generated_keys: Vec<String>,
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Defaulted with `Vec::new` rather than `Default`, which would require `DocumentT: Default`.
+    #[serde(default = "Vec::new")]
+    pub changes: Vec<Change<DocumentT>>,
+}
+
+/// The status of a secondary index, returned by `index_status` and `index_wait`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct IndexStatus {
+    pub index: String,
+    pub ready: bool,
+    /// How far along the index's construction is, between 0 and 1; only sent while not `ready`.
+    pub progress: Option<f64>,
+    /// The index function, in an opaque serialized form.
+    pub function: ReqlBinary,
+    pub multi: bool,
+    pub geo: bool,
+    /// Whether the index was created with an older version of the server and should be rebuilt.
+    pub outdated: bool,
+    /// The query which would create the index, as JavaScript.
+    pub query: String,
+}
+
+/// The configuration of a database, returned by `config` on a database.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct DbConfig {
+    pub id: String,
+    pub name: String,
+}
+
+/// The configuration of a table, returned by `config` on a table and in `ReconfigureResult`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableConfig {
+    pub id: String,
+    pub name: String,
+    pub db: String,
+    pub primary_key: String,
+    pub shards: Vec<ShardConfig>,
+    pub indexes: Vec<String>,
+    /// `"majority"` or `"single"`.
+    pub write_acks: String,
+    /// `"hard"` or `"soft"`.
+    pub durability: String,
+}
+
+/// The servers holding one shard of a table, by name.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShardConfig {
+    pub primary_replica: String,
+    pub replicas: Vec<String>,
+    #[serde(default)]
+    pub nonvoting_replicas: Vec<String>,
+}
+
+/// The status of a table, returned by `status` and in `ReconfigureResult` and `RebalanceResult`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TableStatus {
+    pub id: String,
+    pub name: String,
+    pub db: String,
+    pub status: TableReadiness,
+    /// `None` if the table is unavailable.
+    pub shards: Option<Vec<ShardStatus>>,
+    pub raft_leader: Option<String>,
+}
+
+/// How ready a table is, from serving outdated reads up to having every replica ready.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct TableReadiness {
+    pub ready_for_outdated_reads: bool,
+    pub ready_for_reads: bool,
+    pub ready_for_writes: bool,
+    pub all_replicas_ready: bool,
+}
+
+/// The state of the replicas of one shard of a table.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ShardStatus {
+    pub primary_replicas: Vec<String>,
+    pub replicas: Vec<ReplicaStatus>,
+}
+
+/// The state of one replica of a shard, by server name.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReplicaStatus {
+    pub server: String,
+    pub state: ReplicaState,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplicaState {
+    Ready,
+    Transitioning,
+    Backfilling,
+    Disconnected,
+    WaitingForPrimary,
+    WaitingForQuorum,
+}
+
+/// The result of `wait`: the number of tables which are now ready.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct WaitResult {
+    pub ready: u64,
+}
+
+/// The result of `reconfigure` and `emergency_repair`. With the `dry_run` option nothing is
+/// `reconfigured` (or `repaired`), and the changes are the ones which would have been made.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReconfigureResult {
+    #[serde(default)]
+    pub reconfigured: u64,
+    /// Only sent by `emergency_repair`.
+    #[serde(default)]
+    pub repaired: u64,
+    pub config_changes: Vec<Change<TableConfig>>,
+    /// Not sent with the `dry_run` option.
+    #[serde(default)]
+    pub status_changes: Vec<Change<TableStatus>>,
+}
+
+/// The result of `rebalance`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RebalanceResult {
+    pub rebalanced: u64,
+    pub status_changes: Vec<Change<TableStatus>>,
+}
+
+/// One result of `get_nearest`: a document and its distance from the point.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Nearest<DocumentT> {
+    pub dist: f64,
+    pub doc: DocumentT,
+}
+
+/// The result of `match_`: the matched substring, its position and the capture groups. Groups
+/// which did not participate in the match are `None`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegexMatch {
+    pub str: String,
+    pub start: u64,
+    pub end: u64,
+    pub groups: Vec<Option<RegexGroup>>,
+}
+
+/// A capture group of a `RegexMatch`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RegexGroup {
+    pub str: String,
+    pub start: u64,
+    pub end: u64,
+}
+
+/// Binary data, the `BINARY` pseudo-type, which carries the bytes base64-encoded. Can be used in
+/// queries (as a `BinaryOut`, see `r::binary`) and in documents, and read back from query results.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ReqlBinary(pub Vec<u8>);
+
+impl ReqlBinary {
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for ReqlBinary {
+    fn from(bytes: Vec<u8>) -> Self {
+        ReqlBinary(bytes)
+    }
+}
+
+impl<'a> From<&'a [u8]> for ReqlBinary {
+    fn from(bytes: &'a [u8]) -> Self {
+        ReqlBinary(bytes.to_owned())
+    }
+}
+
+impl Deref for ReqlBinary {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+impl DerefMut for ReqlBinary {
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+impl Serialize for ReqlBinary {
+    fn serialize<SerializerT: Serializer>(
+        &self,
+        serializer: SerializerT,
+    ) -> Result<SerializerT::Ok, SerializerT::Error> {
+        #[derive(Serialize)]
+        struct Raw {
+            #[serde(rename = "$reql_type$")]
+            reql_type: &'static str,
+            data: String,
+        }
+
+        Raw {
+            reql_type: BINARY,
+            data: BASE64.encode(&self.0),
+        }.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ReqlBinary {
+    fn deserialize<DeserializerT: Deserializer<'de>>(
+        deserializer: DeserializerT,
+    ) -> Result<Self, DeserializerT::Error> {
+        #[derive(Deserialize)]
+        struct Raw {
+            #[serde(rename = "$reql_type$")]
+            reql_type: String,
+            data: String,
+        }
+
+        let raw = Raw::deserialize(deserializer)?;
+        if raw.reql_type != BINARY {
+            return Err(DeserializerT::Error::custom(format!(
+                "expected {} pseudo-type, got {}",
+                BINARY, raw.reql_type
+            )));
+        }
+        // Be lenient with whitespace in the base64 data, as decoders in other drivers are.
+        let data: String = raw.data.split_whitespace().collect();
+        BASE64
+            .decode(&data)
+            .map(ReqlBinary)
+            .map_err(|error| DeserializerT::Error::custom(format!("invalid base64: {}", error)))
+    }
+}
+
+const BINARY: &str = "BINARY";
+const GEOMETRY: &str = "GEOMETRY";
+const GROUPED_DATA: &str = "GROUPED_DATA";
+const TIME: &str = "TIME";
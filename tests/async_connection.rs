@@ -0,0 +1,144 @@
+#![cfg(feature = "async")]
+
+extern crate base64;
+extern crate byteorder;
+extern crate futures_util;
+extern crate hmac;
+extern crate pbkdf2;
+extern crate rethink;
+#[macro_use]
+extern crate serde_json;
+extern crate sha2;
+extern crate tokio;
+
+mod support;
+
+use futures_util::future;
+use futures_util::StreamExt;
+use rethink::async_connection::AsyncCursor;
+use rethink::{AsyncConnection, ErrorKind};
+use std::net::SocketAddr;
+use support::{
+    Frame, CONTINUE, START, STOP, SUCCESS_ATOM, SUCCESS_PARTIAL, SUCCESS_SEQUENCE,
+};
+use tokio::runtime::{Builder, Runtime};
+
+fn runtime() -> Runtime {
+    Builder::new_current_thread().enable_all().build().unwrap()
+}
+
+fn connect(runtime: &Runtime, address: SocketAddr) -> AsyncConnection {
+    runtime.block_on(AsyncConnection::connect(address)).unwrap()
+}
+
+fn collect(runtime: &Runtime, cursor: AsyncCursor<u64>) -> Vec<u64> {
+    runtime
+        .block_on(cursor.collect::<Vec<_>>())
+        .into_iter()
+        .map(Result::unwrap)
+        .collect()
+}
+
+#[test]
+fn partial_batches_are_continued_until_the_sequence_ends() {
+    let (address, server) = support::serve(|peer| {
+        let start = peer.expect(START);
+        peer.respond(start.token, SUCCESS_PARTIAL, json!([1, 2]));
+        let first = peer.expect(CONTINUE);
+        peer.respond(start.token, SUCCESS_PARTIAL, json!([3]));
+        let second = peer.expect(CONTINUE);
+        peer.respond(start.token, SUCCESS_SEQUENCE, json!([4]));
+        (start.token, vec![first, second], peer.drain())
+    });
+
+    let runtime = runtime();
+    let connection = connect(&runtime, address);
+    let cursor = connection.run(json!([2, [1, 2, 3, 4]])).unwrap();
+    assert_eq!(collect(&runtime, cursor), vec![1, 2, 3, 4]);
+    drop(connection);
+    drop(runtime);
+
+    let (token, continues, rest) = server.join().unwrap();
+    assert!(continues.iter().all(|frame| frame.token == token));
+    assert_eq!(rest, Vec::<Frame>::new());
+}
+
+#[test]
+fn dropped_cursor_is_stopped_with_its_token() {
+    let (address, server) = support::serve(|peer| {
+        let abandoned = peer.expect(START);
+        peer.respond(abandoned.token, SUCCESS_PARTIAL, json!([1, 2]));
+        let stop = peer.expect(STOP);
+        peer.respond(stop.token, SUCCESS_SEQUENCE, json!([]));
+        let next = peer.expect(START);
+        peer.respond(next.token, SUCCESS_ATOM, json!([3]));
+        (abandoned.token, stop.token, peer.drain())
+    });
+
+    let runtime = runtime();
+    let connection = connect(&runtime, address);
+    let mut cursor: AsyncCursor<u64> = connection.run(json!([2, [1, 2, 3]])).unwrap();
+    assert_eq!(runtime.block_on(cursor.next()).unwrap().unwrap(), 1);
+    drop(cursor);
+
+    let cursor = connection.run(json!(3)).unwrap();
+    assert_eq!(collect(&runtime, cursor), vec![3]);
+    drop(connection);
+    drop(runtime);
+
+    let (abandoned, stopped, rest) = server.join().unwrap();
+    assert_eq!(stopped, abandoned);
+    assert_eq!(rest, Vec::<Frame>::new());
+}
+
+#[test]
+fn interleaved_responses_are_routed_by_token() {
+    let (address, server) = support::serve(|peer| {
+        let first = peer.expect(START);
+        let second = peer.expect(START);
+        peer.respond(second.token, SUCCESS_PARTIAL, json!([10]));
+        peer.respond(first.token, SUCCESS_PARTIAL, json!([1]));
+        let continues = [peer.expect(CONTINUE), peer.expect(CONTINUE)];
+        peer.respond(second.token, SUCCESS_SEQUENCE, json!([20, 30]));
+        peer.respond(first.token, SUCCESS_SEQUENCE, json!([2]));
+        let mut continued: Vec<_> = continues.iter().map(|frame| frame.token).collect();
+        continued.sort();
+        assert_eq!(continued, vec![first.token, second.token]);
+        peer.drain()
+    });
+
+    let runtime = runtime();
+    let connection = connect(&runtime, address);
+    let first: AsyncCursor<u64> = connection.run(json!([2, [1, 2]])).unwrap();
+    let second: AsyncCursor<u64> = connection.run(json!([2, [10, 20, 30]])).unwrap();
+    let (first, second) = runtime.block_on(future::join(
+        first.map(Result::unwrap).collect::<Vec<_>>(),
+        second.map(Result::unwrap).collect::<Vec<_>>(),
+    ));
+    assert_eq!(first, vec![1, 2]);
+    assert_eq!(second, vec![10, 20, 30]);
+    drop(connection);
+    drop(runtime);
+
+    assert_eq!(server.join().unwrap(), Vec::<Frame>::new());
+}
+
+#[test]
+fn pending_cursors_fail_when_the_server_hangs_up() {
+    let (address, server) = support::serve(|peer| {
+        peer.expect(START);
+    });
+
+    let runtime = runtime();
+    let connection = connect(&runtime, address);
+    let mut cursor: AsyncCursor<u64> = connection.run(json!(1)).unwrap();
+    let error = runtime.block_on(cursor.next()).unwrap().unwrap_err();
+    match *error.kind() {
+        ErrorKind::Connection(_) => {}
+        ref kind => panic!("unexpected error: {}", kind),
+    }
+    assert!(runtime.block_on(cursor.next()).is_none());
+    assert!(!connection.is_open());
+    assert!(connection.run::<u64, _>(json!(2)).is_err());
+    server.join().unwrap();
+}
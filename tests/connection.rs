@@ -0,0 +1,177 @@
+extern crate base64;
+extern crate byteorder;
+extern crate hmac;
+extern crate pbkdf2;
+extern crate rethink;
+#[macro_use]
+extern crate serde_json;
+extern crate sha2;
+
+mod support;
+
+use rethink::types::ReqlBinary;
+use rethink::{BinaryFormat, Connection, GlobalOptions, RawConnection, Wait};
+use support::{
+    Frame, CONTINUE, START, STOP, SUCCESS_ATOM, SUCCESS_PARTIAL, SUCCESS_SEQUENCE,
+};
+
+fn connect(address: std::net::SocketAddr) -> Connection {
+    Connection::from_raw(RawConnection::connect(address).unwrap())
+}
+
+fn drain(connection: &mut Connection, cursor: &mut rethink::connection::Cursor) -> Vec<u64> {
+    let mut documents = Vec::new();
+    while let Some(document) = connection.next(Wait::Yes, cursor).unwrap() {
+        documents.push(document);
+    }
+    documents
+}
+
+#[test]
+fn partial_batches_are_continued_until_the_sequence_ends() {
+    let (address, server) = support::serve(|peer| {
+        let start = peer.expect(START);
+        peer.respond(start.token, SUCCESS_PARTIAL, json!([1, 2]));
+        let first = peer.expect(CONTINUE);
+        peer.respond(start.token, SUCCESS_PARTIAL, json!([3]));
+        let second = peer.expect(CONTINUE);
+        peer.respond(start.token, SUCCESS_SEQUENCE, json!([4]));
+        (start.token, vec![first, second], peer.drain())
+    });
+
+    let mut connection = connect(address);
+    let mut cursor = connection.run(json!([2, [1, 2, 3, 4]])).unwrap();
+    assert_eq!(drain(&mut connection, &mut cursor), vec![1, 2, 3, 4]);
+    assert!(cursor.is_exhausted());
+    connection.close().unwrap();
+
+    let (token, continues, rest) = server.join().unwrap();
+    assert!(continues.iter().all(|frame| frame.token == token));
+    assert_eq!(rest, Vec::<Frame>::new());
+}
+
+#[test]
+fn complete_sequences_are_not_continued() {
+    let (address, server) = support::serve(|peer| {
+        let start = peer.expect(START);
+        peer.respond(start.token, SUCCESS_SEQUENCE, json!([1, 2, 3]));
+        peer.drain()
+    });
+
+    let mut connection = connect(address);
+    let mut cursor = connection.run(json!([2, [1, 2, 3]])).unwrap();
+    assert_eq!(drain(&mut connection, &mut cursor), vec![1, 2, 3]);
+    assert!(cursor.is_exhausted());
+    assert!(connection.next::<u64>(Wait::Yes, &mut cursor).is_err());
+    connection.close().unwrap();
+
+    assert_eq!(server.join().unwrap(), Vec::<Frame>::new());
+}
+
+#[test]
+fn continue_is_only_sent_once_the_batch_is_consumed() {
+    let (address, server) = support::serve(|peer| {
+        let start = peer.expect(START);
+        peer.respond(start.token, SUCCESS_PARTIAL, json!([1, 2]));
+        let frames = peer.drain();
+        (start.token, frames)
+    });
+
+    let mut connection = connect(address);
+    let mut cursor = connection.run(json!([2, [1, 2]])).unwrap();
+    assert_eq!(connection.next(Wait::Yes, &mut cursor).unwrap(), Some(1));
+    assert_eq!(connection.next(Wait::Yes, &mut cursor).unwrap(), Some(2));
+    assert!(!cursor.is_exhausted());
+    connection.stop(&mut cursor).unwrap();
+    connection.close().unwrap();
+
+    // The only frame after the START is the STOP, no CONTINUE was sent for the unread batch.
+    let (token, frames) = server.join().unwrap();
+    assert_eq!(frames.len(), 1);
+    assert_eq!(frames[0].token, token);
+    assert_eq!(frames[0].query_type, STOP);
+}
+
+#[test]
+fn dropped_partial_cursor_is_stopped_with_its_token() {
+    let (address, server) = support::serve(|peer| {
+        let abandoned = peer.expect(START);
+        peer.respond(abandoned.token, SUCCESS_PARTIAL, json!([1, 2]));
+        let stop = peer.expect(STOP);
+        peer.respond(stop.token, SUCCESS_SEQUENCE, json!([]));
+        let next = peer.expect(START);
+        peer.respond(next.token, SUCCESS_ATOM, json!(["next"]));
+        (abandoned.token, stop.token, peer.drain())
+    });
+
+    let mut connection = connect(address);
+    let mut cursor = connection.run(json!([2, [1, 2, 3]])).unwrap();
+    assert_eq!(connection.next(Wait::Yes, &mut cursor).unwrap(), Some(1));
+    drop(cursor);
+
+    let mut cursor = connection.run(json!("next")).unwrap();
+    assert_eq!(
+        connection.next(Wait::Yes, &mut cursor).unwrap(),
+        Some("next".to_owned())
+    );
+    connection.close().unwrap();
+
+    let (abandoned, stopped, rest) = server.join().unwrap();
+    assert_eq!(stopped, abandoned);
+    assert_eq!(rest, Vec::<Frame>::new());
+}
+
+#[test]
+fn dropped_complete_or_exhausted_cursors_are_not_stopped() {
+    let (address, server) = support::serve(|peer| {
+        let complete = peer.expect(START);
+        peer.respond(complete.token, SUCCESS_SEQUENCE, json!([1, 2]));
+        let exhausted = peer.expect(START);
+        peer.respond(exhausted.token, SUCCESS_ATOM, json!([3]));
+        let last = peer.expect(START);
+        peer.respond(last.token, SUCCESS_ATOM, json!([4]));
+        peer.drain()
+    });
+
+    let mut connection = connect(address);
+    let mut complete = connection.run(json!([2, [1, 2]])).unwrap();
+    assert_eq!(connection.next(Wait::Yes, &mut complete).unwrap(), Some(1));
+    drop(complete);
+
+    let mut exhausted = connection.run(json!(3)).unwrap();
+    assert_eq!(drain(&mut connection, &mut exhausted), vec![3]);
+    assert!(exhausted.is_exhausted());
+    drop(exhausted);
+
+    let mut last = connection.run(json!(4)).unwrap();
+    assert_eq!(connection.next(Wait::Yes, &mut last).unwrap(), Some(4));
+    connection.close().unwrap();
+
+    assert_eq!(server.join().unwrap(), Vec::<Frame>::new());
+}
+
+#[test]
+fn global_options_are_sent_with_the_start_frame() {
+    let (address, server) = support::serve(|peer| {
+        let start = peer.expect(START);
+        peer.respond(
+            start.token,
+            SUCCESS_ATOM,
+            json!([{"$reql_type$": "BINARY", "data": "cmV0aGluaw=="}]),
+        );
+        (start, peer.drain())
+    });
+
+    let mut connection = connect(address);
+    let options = GlobalOptions::default().binary_format(BinaryFormat::Raw);
+    let mut cursor = connection.run_with(json!("rethink"), &options).unwrap();
+    assert_eq!(
+        connection.next(Wait::Yes, &mut cursor).unwrap(),
+        Some(ReqlBinary(b"rethink".to_vec()))
+    );
+    connection.close().unwrap();
+
+    let (start, rest) = server.join().unwrap();
+    assert_eq!(start.query, json!([START, "rethink", {"binary_format": "raw"}]));
+    assert_eq!(rest, Vec::<Frame>::new());
+}
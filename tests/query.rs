@@ -0,0 +1,863 @@
+extern crate rethink;
+#[macro_use]
+extern crate serde_json;
+
+use rethink::query::{self as r, IntoFunctionExpr, Null, NumberOut, StringOut, Var};
+use rethink::types::ReqlTime;
+use serde_json::Value;
+
+const ADD: u64 = 24;
+const GET_FIELD: u64 = 31;
+const MAP: u64 = 38;
+const FILTER: u64 = 39;
+const FUNC: u64 = 69;
+const VAR: u64 = 10;
+const MAKE_ARRAY: u64 = 2;
+const TABLE: u64 = 15;
+const GET: u64 = 16;
+const CHANGES: u64 = 152;
+const ORDER_BY: u64 = 41;
+const LIMIT: u64 = 71;
+const ASC: u64 = 73;
+const DESC: u64 = 74;
+const REDUCE: u64 = 37;
+const COUNT: u64 = 43;
+const GROUP: u64 = 144;
+const UNGROUP: u64 = 150;
+const FOLD: u64 = 187;
+const INNER_JOIN: u64 = 48;
+const EQ_JOIN: u64 = 50;
+const ZIP: u64 = 72;
+const EQ: u64 = 17;
+const ISO8601: u64 = 99;
+const EPOCH_TIME: u64 = 101;
+const NOW: u64 = 103;
+const IN_TIMEZONE: u64 = 104;
+const DURING: u64 = 105;
+const MONDAY: u64 = 107;
+const JANUARY: u64 = 114;
+const YEAR: u64 = 128;
+const DAY_OF_WEEK: u64 = 131;
+const TIME: u64 = 136;
+const ARGS: u64 = 154;
+const POINT: u64 = 159;
+const LINE: u64 = 160;
+const DISTANCE: u64 = 162;
+const CIRCLE: u64 = 165;
+const GET_INTERSECTING: u64 = 166;
+const FILL: u64 = 167;
+const GET_NEAREST: u64 = 168;
+const HAS_FIELDS: u64 = 32;
+const PLUCK: u64 = 33;
+const WITHOUT: u64 = 34;
+const MERGE: u64 = 35;
+const OBJECT: u64 = 143;
+const LITERAL: u64 = 137;
+const APPEND: u64 = 29;
+const PREPEND: u64 = 80;
+const INSERT_AT: u64 = 82;
+const DELETE_AT: u64 = 83;
+const CHANGE_AT: u64 = 84;
+const SPLICE_AT: u64 = 85;
+const SET_INSERT: u64 = 88;
+const SET_UNION: u64 = 90;
+const DIFFERENCE: u64 = 95;
+const NE: u64 = 18;
+const MATCH: u64 = 97;
+const SPLIT: u64 = 149;
+const ERROR: u64 = 12;
+const GT: u64 = 21;
+const INSERT: u64 = 56;
+const FUNCALL: u64 = 64;
+const BRANCH: u64 = 65;
+const FOR_EACH: u64 = 68;
+const RANGE: u64 = 173;
+const LT: u64 = 19;
+const SUB: u64 = 25;
+const AND: u64 = 67;
+const NOT: u64 = 23;
+const DIV: u64 = 27;
+const MOD: u64 = 28;
+const RANDOM: u64 = 151;
+const FLOOR: u64 = 183;
+const CEIL: u64 = 184;
+const ROUND: u64 = 185;
+const REPLACE: u64 = 55;
+const UPDATE: u64 = 53;
+const CONFIG: u64 = 174;
+const STATUS: u64 = 175;
+const WAIT: u64 = 177;
+const REBALANCE: u64 = 179;
+const INDEX_CREATE: u64 = 75;
+const INDEX_RENAME: u64 = 156;
+const RECONFIGURE: u64 = 176;
+const DEFAULT: u64 = 92;
+
+/// Splits a serialized `FUNC` term into its parameter ids and its body.
+fn function(term: &Value) -> (Vec<u64>, &Value) {
+    assert_eq!(term[0], FUNC, "not a function: {}", term);
+    let parameters = term[1][0][1]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|id| id.as_u64().unwrap())
+        .collect();
+    (parameters, &term[1][1])
+}
+
+fn var(id: u64) -> Value {
+    json!([VAR, [id]])
+}
+
+#[test]
+fn nested_lambdas_bind_distinct_vars() {
+    let query = r::expr([1, 2, 3]).map(|x| r::expr([4, 5]).filter(move |y| y.eq(x)));
+    let query = serde_json::to_value(&query).unwrap();
+
+    assert_eq!(query[0], MAP);
+    let (outer, outer_body) = function(&query[1][1]);
+    assert_eq!(outer_body[0], FILTER);
+    let (inner, inner_body) = function(&outer_body[1][1]);
+    let (outer, inner) = (outer[0], inner[0]);
+
+    assert_ne!(outer, inner);
+    assert_eq!(inner_body[1][0], var(inner));
+    assert_eq!(inner_body[1][1], var(outer));
+}
+
+#[test]
+fn sibling_lambdas_bind_distinct_vars() {
+    let query = r::expr([1, 2, 3])
+        .map(|x| x.add(1))
+        .filter(|y| y.ne(2));
+    let query = serde_json::to_value(&query).unwrap();
+
+    assert_eq!(query[0], FILTER);
+    let (filter, filter_body) = function(&query[1][1]);
+    assert_eq!(query[1][0][0], MAP);
+    let (map, map_body) = function(&query[1][0][1][1]);
+    let (filter, map) = (filter[0], map[0]);
+
+    assert_ne!(filter, map);
+    assert_eq!(filter_body[1][0], var(filter));
+    assert_eq!(map_body[1][0], var(map));
+}
+
+#[test]
+fn multi_argument_lambdas_bind_one_var_per_argument() {
+    let function = (|a: Var<NumberOut>, b: Var<NumberOut>| a.add(b)).into_function_expr();
+    let function = serde_json::to_value(&function).unwrap();
+    let (parameters, body) = self::function(&function);
+
+    assert_eq!(parameters.len(), 2);
+    assert_ne!(parameters[0], parameters[1]);
+    assert_eq!(*body, json!([ADD, [var(parameters[0]), var(parameters[1])]]));
+}
+
+#[test]
+fn five_argument_lambdas_bind_distinct_vars() {
+    type S = Var<StringOut>;
+    let function =
+        (|a: S, b: S, c: S, d: S, e: S| a.add(b).add(c).add(d).add(e)).into_function_expr();
+    let function = serde_json::to_value(&function).unwrap();
+    let (mut parameters, _) = self::function(&function);
+
+    parameters.sort();
+    parameters.dedup();
+    assert_eq!(parameters.len(), 5);
+}
+
+#[test]
+fn operators_build_the_same_terms_as_methods() {
+    let a = || r::expr(1);
+    let b = || r::expr(true);
+    let operators = (
+        (a() + 2) * a() - 3.5 / a() % 4,
+        1 + a(),
+        !b() & false | b(),
+        "a" + r::expr("b"),
+    );
+    let methods = (
+        a().add(2).mul(a()).sub(r::expr(3.5).div(a()).modulo(4)),
+        r::expr(1).add(a()),
+        b().not().and(false).or(b()),
+        r::expr("a").add("b"),
+    );
+
+    assert_eq!(
+        serde_json::to_value(&operators).unwrap(),
+        serde_json::to_value(&methods).unwrap()
+    );
+}
+
+#[test]
+fn right_of_an_outer_join_defaults_to_null() {
+    let query = r::expr([1, 2])
+        .outer_join(r::expr([2, 3]), |left, right| left.eq(right))
+        .map(|pair| pair.right());
+    let query = serde_json::to_value(&query).unwrap();
+
+    assert_eq!(query[0], MAP);
+    let (pair, body) = function(&query[1][1]);
+    assert_eq!(
+        *body,
+        json!([DEFAULT, [[GET_FIELD, [var(pair[0]), "right"]], null]])
+    );
+}
+
+#[test]
+fn joins_serialize_their_sides_and_options() {
+    let users = r::db("test").table("users");
+    let teams = r::db("test").table("teams");
+    let (users_ast, teams_ast) = (
+        serde_json::to_value(&users).unwrap(),
+        serde_json::to_value(&teams).unwrap(),
+    );
+
+    let zipped = users
+        .eq_join("team", teams)
+        .in_index("name")
+        .with_ordered(true)
+        .zip();
+    assert_eq!(
+        serde_json::to_value(&zipped).unwrap(),
+        json!([
+            ZIP,
+            [[EQ_JOIN, [users_ast, "team", teams_ast], {"index": "name", "ordered": true}]]
+        ])
+    );
+
+    let inner = r::expr([1, 2])
+        .inner_join(r::expr([2, 3]), |left, right| left.eq(right))
+        .map(|pair| pair.right());
+    let inner = serde_json::to_value(&inner).unwrap();
+    assert_eq!(inner[1][0][0], INNER_JOIN);
+    let (pair, body) = function(&inner[1][1]);
+    assert_eq!(*body, json!([GET_FIELD, [var(pair[0]), "right"]]));
+}
+
+#[test]
+fn default_accepts_untyped_and_nullable_values() {
+    let field = r::db("test").table("users").get("ada").g("age").default(0);
+    let field = serde_json::to_value(&field).unwrap();
+    assert_eq!(field[0], DEFAULT);
+    assert_eq!(field[1][0][0], GET_FIELD);
+    assert_eq!(field[1][1], 0);
+
+    let nullable = r::null::<NumberOut>().default(1).add(1);
+    assert_eq!(
+        serde_json::to_value(&nullable).unwrap(),
+        json!([ADD, [[DEFAULT, [null, 1]], 1]])
+    );
+
+    let still_nullable = r::null::<NumberOut>().default(r::null());
+    assert_eq!(
+        serde_json::to_value(&still_nullable).unwrap(),
+        json!([DEFAULT, [null, null]])
+    );
+}
+
+#[test]
+fn field_accesses_chain_like_indexing() {
+    let nested = r::db("test").table("users").get("ada").g("address").g("city");
+    let nested = serde_json::to_value(&nested).unwrap();
+
+    assert_eq!(nested[0], GET_FIELD);
+    assert_eq!(nested[1][1], "city");
+    assert_eq!(nested[1][0][0], GET_FIELD);
+    assert_eq!(nested[1][0][1][1], "address");
+}
+
+#[test]
+fn compound_index_returns_the_fields_of_the_document() {
+    let query = r::db("test")
+        .table("users")
+        .index_create_compound("full_name", ["last", "first"]);
+    let query = serde_json::to_value(&query).unwrap();
+
+    assert_eq!(query[0], INDEX_CREATE);
+    assert_eq!(query[1][0][0], TABLE);
+    assert_eq!(query[1][1], "full_name");
+    let (document, body) = function(&query[1][2]);
+    let document = var(document[0]);
+    assert_eq!(
+        *body,
+        json!([
+            MAKE_ARRAY,
+            [
+                [GET_FIELD, [document.clone(), "last"]],
+                [GET_FIELD, [document, "first"]]
+            ]
+        ])
+    );
+}
+
+#[test]
+fn index_options_are_serialized() {
+    let users = r::db("test").table("users");
+
+    let multi = serde_json::to_value(&users.index_create("tags").with_multi(true)).unwrap();
+    assert_eq!(multi[0], INDEX_CREATE);
+    assert_eq!(multi[1][1], "tags");
+    assert_eq!(multi[2], json!({ "multi": true }));
+
+    let rename = users.index_rename("tags", "labels").with_overwrite(true);
+    let rename = serde_json::to_value(&rename).unwrap();
+    assert_eq!(rename[0], INDEX_RENAME);
+    assert_eq!(rename[1][1], "tags");
+    assert_eq!(rename[1][2], "labels");
+    assert_eq!(rename[2], json!({ "overwrite": true }));
+}
+
+#[test]
+fn reconfigure_and_emergency_repair_pass_options() {
+    let users = r::db("test").table("users");
+
+    let reconfigure = serde_json::to_value(&users.reconfigure(2, 3)).unwrap();
+    assert_eq!(reconfigure[0], RECONFIGURE);
+    assert_eq!(reconfigure[1][0][0], TABLE);
+    assert_eq!(reconfigure[2], json!({ "shards": 2, "replicas": 3 }));
+
+    let repair = users.emergency_repair("unsafe_rollback").with_dry_run(true);
+    let repair = serde_json::to_value(&repair).unwrap();
+    assert_eq!(repair[0], RECONFIGURE);
+    assert_eq!(repair[1].as_array().unwrap().len(), 1);
+    assert_eq!(
+        repair[2],
+        json!({ "emergency_repair": "unsafe_rollback", "dry_run": true })
+    );
+}
+
+#[test]
+fn changes_pass_feed_options() {
+    let users = r::db("test").table("users");
+
+    let feed = users
+        .changes()
+        .with_squash(0.5)
+        .with_include_initial(true)
+        .with_include_states(true)
+        .with_include_types(true)
+        .with_changefeed_queue_size(1000);
+    let feed = serde_json::to_value(&feed).unwrap();
+    assert_eq!(feed[0], CHANGES);
+    assert_eq!(feed[1][0][0], TABLE);
+    assert_eq!(
+        feed[2],
+        json!({
+            "squash": 0.5,
+            "include_initial": true,
+            "include_states": true,
+            "include_types": true,
+            "changefeed_queue_size": 1000,
+        })
+    );
+
+    let document = serde_json::to_value(&users.get("ada").changes()).unwrap();
+    assert_eq!(document[0], CHANGES);
+    assert_eq!(document[1][0][0], GET);
+    assert_eq!(document[1][0][1][1], "ada");
+}
+
+#[test]
+fn order_by_serializes_keys_and_index_orderings() {
+    let users = || r::db("test").table("users");
+    let table = serde_json::to_value(&users()).unwrap();
+
+    let by_keys = serde_json::to_value(&users().order_by((r::desc("score"), "name"))).unwrap();
+    assert_eq!(
+        by_keys,
+        json!([ORDER_BY, [table.clone(), [DESC, ["score"]], "name"]])
+    );
+
+    let by_function = users().order_by(r::asc_with(|user: Var<_>| user.g("score")));
+    let by_function = serde_json::to_value(&by_function).unwrap();
+    assert_eq!(by_function[1][1][0], ASC);
+    let (user, body) = function(&by_function[1][1][1][0]);
+    assert_eq!(*body, json!([GET_FIELD, [var(user[0]), "score"]]));
+
+    let feed = users().order_by_index(r::desc("id")).limit(3).changes();
+    assert_eq!(
+        serde_json::to_value(&feed).unwrap(),
+        json!([
+            CHANGES,
+            [[LIMIT, [[ORDER_BY, [table], {"index": [DESC, ["id"]]}], 3]]]
+        ])
+    );
+}
+
+#[test]
+fn group_serializes_fields_functions_and_options() {
+    let users = || r::db("test").table("users");
+    let table = serde_json::to_value(&users()).unwrap();
+
+    let counted = users().group("team").count().ungroup();
+    assert_eq!(
+        serde_json::to_value(&counted).unwrap(),
+        json!([UNGROUP, [[COUNT, [[GROUP, [table.clone(), "team"]]]]]])
+    );
+
+    let by_index = users().group_by_index("tags").with_multi(true);
+    assert_eq!(
+        serde_json::to_value(&by_index).unwrap(),
+        json!([GROUP, [table], {"index": "tags", "multi": true}])
+    );
+
+    let by_function = users().group_with(|user| user.g("age").as_number().add(1));
+    let by_function = serde_json::to_value(&by_function).unwrap();
+    assert_eq!(by_function[0], GROUP);
+    let (user, body) = function(&by_function[1][1]);
+    assert_eq!(body[1][0], json!([GET_FIELD, [var(user[0]), "age"]]));
+}
+
+#[test]
+fn reduce_and_fold_pass_their_accumulators() {
+    let reduced = r::expr([1, 2, 3]).reduce(|a, b| a.add(b));
+    let reduced = serde_json::to_value(&reduced).unwrap();
+    assert_eq!(reduced[0], REDUCE);
+    let (pair, body) = function(&reduced[1][1]);
+    assert_eq!(pair.len(), 2);
+    assert_eq!(*body, json!([ADD, [var(pair[0]), var(pair[1])]]));
+
+    let folded = r::expr([1, 2, 3]).fold(0, |total, x| total.add(x));
+    let folded = serde_json::to_value(&folded).unwrap();
+    assert_eq!(folded[0], FOLD);
+    assert_eq!(folded[1][1], 0);
+    let (pair, body) = function(&folded[1][2]);
+    assert_eq!(*body, json!([ADD, [var(pair[0]), var(pair[1])]]));
+
+    let emitted = r::expr([1, 2, 3])
+        .fold_emit(0, |total, x| total.add(x), |_, _, total| r::expr([total]))
+        .with_final_emit(|total| r::expr([total]));
+    let emitted = serde_json::to_value(&emitted).unwrap();
+    assert_eq!(emitted[0], FOLD);
+    let (emit, body) = function(&emitted[2]["emit"]);
+    assert_eq!(emit.len(), 3);
+    assert_eq!(*body, json!([MAKE_ARRAY, [var(emit[2])]]));
+    let (last, body) = function(&emitted[2]["final_emit"]);
+    assert_eq!(*body, json!([MAKE_ARRAY, [var(last[0])]]));
+}
+
+#[test]
+fn time_terms_serialize_with_their_options() {
+    assert_eq!(
+        serde_json::to_value(&r::time(2018, r::january(), 1, "Z")).unwrap(),
+        json!([TIME, [2018, [JANUARY, []], 1, "Z"]])
+    );
+    assert_eq!(
+        serde_json::to_value(&r::iso8601("2018-01-01T00:00").with_default_timezone("-05:00"))
+            .unwrap(),
+        json!([ISO8601, ["2018-01-01T00:00"], {"default_timezone": "-05:00"}])
+    );
+
+    let during = r::now()
+        .in_timezone("+01:00")
+        .during(r::epoch_time(0), r::expr(ReqlTime::utc(60.5)))
+        .with_right_bound("closed");
+    assert_eq!(
+        serde_json::to_value(&during).unwrap(),
+        json!([
+            DURING,
+            [
+                [IN_TIMEZONE, [[NOW, []], "+01:00"]],
+                [EPOCH_TIME, [0]],
+                {"$reql_type$": "TIME", "epoch_time": 60.5, "timezone": "+00:00"}
+            ],
+            {"right_bound": "closed"}
+        ])
+    );
+
+    let accessors = (r::now().year(), r::now().day_of_week().eq(r::monday()));
+    assert_eq!(
+        serde_json::to_value(&accessors).unwrap(),
+        json!([
+            [YEAR, [[NOW, []]]],
+            [EQ, [[DAY_OF_WEEK, [[NOW, []]]], [MONDAY, []]]]
+        ])
+    );
+}
+
+#[test]
+fn geometry_terms_serialize_with_their_options() {
+    let line = r::line([[0, 0], [1, 1]]).fill();
+    assert_eq!(
+        serde_json::to_value(&line).unwrap(),
+        json!([
+            FILL,
+            [[LINE, [[ARGS, [[MAKE_ARRAY, [[MAKE_ARRAY, [0, 0]], [MAKE_ARRAY, [1, 1]]]]]]]]]
+        ])
+    );
+
+    let circle = r::circle(r::point(-122.4, 37.8), 10)
+        .with_unit("km")
+        .with_num_vertices(8)
+        .with_fill(false);
+    assert_eq!(
+        serde_json::to_value(&circle).unwrap(),
+        json!([
+            CIRCLE,
+            [[POINT, [-122.4, 37.8]], 10],
+            {"unit": "km", "num_vertices": 8, "fill": false}
+        ])
+    );
+
+    let distance = r::point(0, 0)
+        .distance(r::point(1, 1))
+        .with_geo_system("unit_sphere");
+    assert_eq!(
+        serde_json::to_value(&distance).unwrap(),
+        json!([
+            DISTANCE,
+            [[POINT, [0, 0]], [POINT, [1, 1]]],
+            {"geo_system": "unit_sphere"}
+        ])
+    );
+}
+
+#[test]
+fn geospatial_queries_pass_index_options() {
+    let places = || r::db("test").table("places");
+    let table = serde_json::to_value(&places()).unwrap();
+
+    let nearest = places()
+        .get_nearest(r::point(0, 0))
+        .in_index("location")
+        .with_max_dist(5)
+        .with_max_results(3)
+        .with_unit("mi");
+    assert_eq!(
+        serde_json::to_value(&nearest).unwrap(),
+        json!([
+            GET_NEAREST,
+            [table.clone(), [POINT, [0, 0]]],
+            {"index": "location", "max_dist": 5, "max_results": 3, "unit": "mi"}
+        ])
+    );
+
+    let intersecting = places()
+        .get_intersecting(r::circle(r::point(0, 0), 100))
+        .in_index("area");
+    assert_eq!(
+        serde_json::to_value(&intersecting).unwrap(),
+        json!([
+            GET_INTERSECTING,
+            [table, [CIRCLE, [[POINT, [0, 0]], 100]]],
+            {"index": "area"}
+        ])
+    );
+}
+
+#[test]
+fn object_terms_take_selectors_and_literals() {
+    let ada = || r::db("test").table("users").get("ada");
+    let get = serde_json::to_value(&ada()).unwrap();
+
+    let plucked = ada().pluck(r::args(["name", "email"]));
+    assert_eq!(
+        serde_json::to_value(&plucked).unwrap(),
+        json!([PLUCK, [get.clone(), [ARGS, [[MAKE_ARRAY, ["name", "email"]]]]]])
+    );
+    let without = ada().without("password").has_fields("name");
+    assert_eq!(
+        serde_json::to_value(&without).unwrap(),
+        json!([HAS_FIELDS, [[WITHOUT, [get.clone(), "password"]], "name"]])
+    );
+
+    let merged = ada().merge(r::object((
+        ("address", r::literal(r::object((("city", "Paris"),)))),
+        ("nickname", r::literal_empty()),
+    )));
+    assert_eq!(
+        serde_json::to_value(&merged).unwrap(),
+        json!([
+            MERGE,
+            [
+                get,
+                [
+                    OBJECT,
+                    [
+                        "address",
+                        [LITERAL, [[OBJECT, ["city", "Paris"]]]],
+                        "nickname",
+                        [LITERAL, []]
+                    ]
+                ]
+            ]
+        ])
+    );
+}
+
+#[test]
+fn array_terms_serialize_positions_and_operands() {
+    let array = || r::expr([1, 2, 3]);
+    let literal = json!([MAKE_ARRAY, [1, 2, 3]]);
+
+    let edited = array()
+        .insert_at(0, 0)
+        .splice_at(1, r::expr([7, 8]))
+        .delete_at(-1)
+        .delete_range(1, 2)
+        .change_at(0, 9);
+    let inserted = json!([INSERT_AT, [literal.clone(), 0, 0]]);
+    let spliced = json!([SPLICE_AT, [inserted, 1, [MAKE_ARRAY, [7, 8]]]]);
+    let deleted = json!([DELETE_AT, [[DELETE_AT, [spliced, -1]], 1, 2]]);
+    assert_eq!(
+        serde_json::to_value(&edited).unwrap(),
+        json!([CHANGE_AT, [deleted, 0, 9]])
+    );
+
+    let sets = array()
+        .append(4)
+        .prepend(0)
+        .difference(r::expr([2]))
+        .set_insert(5)
+        .set_union(r::expr([6]));
+    assert_eq!(
+        serde_json::to_value(&sets).unwrap(),
+        json!([
+            SET_UNION,
+            [
+                [
+                    SET_INSERT,
+                    [
+                        [DIFFERENCE, [[PREPEND, [[APPEND, [literal, 4]], 0]], [MAKE_ARRAY, [2]]]],
+                        5
+                    ]
+                ],
+                [MAKE_ARRAY, [6]]
+            ]
+        ])
+    );
+}
+
+#[test]
+fn match_filters_by_comparing_with_null() {
+    let query = r::db("test")
+        .table("users")
+        .filter(|doc| doc.get_field("name").as_string().match_("^A").ne(Null));
+    let query = serde_json::to_value(&query).unwrap();
+
+    assert_eq!(query[0], FILTER);
+    let (doc, body) = function(&query[1][1]);
+    assert_eq!(
+        *body,
+        json!([NE, [[MATCH, [[GET_FIELD, [var(doc[0]), "name"]], "^A"]], null]])
+    );
+}
+
+#[test]
+fn split_variants_fill_in_missing_arguments() {
+    let text = || r::expr("a,b c");
+    let splits = (
+        text().split(),
+        text().split_max(1),
+        text().split_on(","),
+        text().split_on_max(",", 1),
+    );
+    assert_eq!(
+        serde_json::to_value(&splits).unwrap(),
+        json!([
+            [SPLIT, ["a,b c"]],
+            [SPLIT, ["a,b c", null, 1]],
+            [SPLIT, ["a,b c", ","]],
+            [SPLIT, ["a,b c", ",", 1]]
+        ])
+    );
+}
+
+#[test]
+fn branch_takes_a_null_opposite_a_nullable_value() {
+    let query = r::db("test").table("users").get("ada").do_(|doc| {
+        r::branch(
+            doc.g("adult").as_bool(),
+            doc.g("name").as_string().as_null_or(),
+            r::null(),
+        )
+    });
+    let query = serde_json::to_value(&query).unwrap();
+
+    assert_eq!(query[0], FUNCALL);
+    let (doc, body) = function(&query[1][0]);
+    let doc = var(doc[0]);
+    assert_eq!(
+        *body,
+        json!([
+            BRANCH,
+            [
+                [GET_FIELD, [doc.clone(), "adult"]],
+                [GET_FIELD, [doc, "name"]],
+                null
+            ]
+        ])
+    );
+
+    let failed = r::branch(true, r::expr(1), r::error("unreachable"));
+    assert_eq!(
+        serde_json::to_value(&failed).unwrap(),
+        json!([BRANCH, [true, 1, [ERROR, ["unreachable"]]]])
+    );
+}
+
+#[test]
+fn ranges_and_for_each_serialize_their_bounds() {
+    let ranges = (r::range_infinite(), r::range(3), r::range_between(1, 3));
+    assert_eq!(
+        serde_json::to_value(&ranges).unwrap(),
+        json!([[RANGE, []], [RANGE, [3]], [RANGE, [1, 3]]])
+    );
+
+    let users = r::db("test").table("users");
+    let table = serde_json::to_value(&users).unwrap();
+    let query = r::range(3).for_each(move |n| users.insert(r::object((("n", n),))));
+    let query = serde_json::to_value(&query).unwrap();
+
+    assert_eq!(query[0], FOR_EACH);
+    assert_eq!(query[1][0], json!([RANGE, [3]]));
+    let (n, body) = function(&query[1][1]);
+    assert_eq!(*body, json!([INSERT, [table, [OBJECT, ["n", var(n[0])]]]]));
+}
+
+#[test]
+fn comparisons_take_several_operands_through_args() {
+    let descending = r::expr(3).gt(r::args_of((2, 1)));
+    assert_eq!(
+        serde_json::to_value(&descending).unwrap(),
+        json!([GT, [3, [ARGS, [[MAKE_ARRAY, [2, 1]]]]]])
+    );
+    let ascending = r::expr(1).lt(r::args(r::expr([2, 3])));
+    assert_eq!(
+        serde_json::to_value(&ascending).unwrap(),
+        json!([LT, [1, [ARGS, [[MAKE_ARRAY, [2, 3]]]]]])
+    );
+    let all_equal = r::expr("a").eq(r::args_of(("a", "a"))).and(true).not();
+    assert_eq!(
+        serde_json::to_value(&all_equal).unwrap(),
+        json!([NOT, [[AND, [[EQ, ["a", [ARGS, [[MAKE_ARRAY, ["a", "a"]]]]]], true]]]])
+    );
+}
+
+#[test]
+fn subtracting_times_gives_seconds() {
+    let elapsed: r::Expr<NumberOut, _> = r::now().sub(r::epoch_time(0)).sub(1.5);
+    assert_eq!(
+        serde_json::to_value(&elapsed).unwrap(),
+        json!([SUB, [[SUB, [[NOW, []], [EPOCH_TIME, [0]]]], 1.5]])
+    );
+}
+
+#[test]
+fn random_and_rounding_terms_serialize_their_options() {
+    let randoms = (
+        r::random(),
+        r::random_to(10),
+        r::random_between(-1, 1).with_float(true),
+    );
+    assert_eq!(
+        serde_json::to_value(&randoms).unwrap(),
+        json!([[RANDOM, []], [RANDOM, [10]], [RANDOM, [-1, 1], {"float": true}]])
+    );
+
+    let rounded = (
+        r::expr(7).div(2).floor(),
+        r::expr(7).modulo(2).ceil(),
+        r::expr(-2.5).round(),
+    );
+    assert_eq!(
+        serde_json::to_value(&rounded).unwrap(),
+        json!([
+            [FLOOR, [[DIV, [7, 2]]]],
+            [CEIL, [[MOD, [7, 2]]]],
+            [ROUND, [-2.5]]
+        ])
+    );
+}
+
+#[test]
+fn insert_conflicts_take_a_strategy_or_a_resolver() {
+    let users = || r::db("test").table("users");
+    let table = serde_json::to_value(&users()).unwrap();
+    let ada = || r::object((("id", "ada"),));
+
+    let replacing = users()
+        .insert(ada())
+        .with_conflict("replace")
+        .with_return_changes("always");
+    assert_eq!(
+        serde_json::to_value(&replacing).unwrap(),
+        json!([
+            INSERT,
+            [table.clone(), [OBJECT, ["id", "ada"]]],
+            {"conflict": "replace", "return_changes": "always"}
+        ])
+    );
+
+    let resolved = users()
+        .insert(ada())
+        .with_conflict_with(|_id, old, new| old.merge(new));
+    let resolved = serde_json::to_value(&resolved).unwrap();
+    assert_eq!(resolved[1], json!([table, [OBJECT, ["id", "ada"]]]));
+    let (parameters, body) = function(&resolved[2]["conflict"]);
+    assert_eq!(parameters.len(), 3);
+    assert_eq!(
+        *body,
+        json!([MERGE, [var(parameters[1]), var(parameters[2])]])
+    );
+}
+
+#[test]
+fn replace_takes_a_document_or_a_function_returning_null() {
+    let ada = || r::db("test").table("users").get("ada");
+    let get = serde_json::to_value(&ada()).unwrap();
+
+    let replaced = ada()
+        .replace(r::object((("id", "ada"),)))
+        .with_durability("soft");
+    assert_eq!(
+        serde_json::to_value(&replaced).unwrap(),
+        json!([REPLACE, [get.clone(), [OBJECT, ["id", "ada"]]], {"durability": "soft"}])
+    );
+
+    let expired = ada()
+        .replace_with(|doc| r::branch(doc.g("expired").as_bool(), r::null(), doc.as_null_or()))
+        .with_non_atomic(true);
+    let expired = serde_json::to_value(&expired).unwrap();
+    assert_eq!(expired[1][0], get);
+    assert_eq!(expired[2], json!({"non_atomic": true}));
+    let (doc, body) = function(&expired[1][1]);
+    let doc = var(doc[0]);
+    assert_eq!(
+        *body,
+        json!([BRANCH, [[GET_FIELD, [doc.clone(), "expired"]], null, doc]])
+    );
+}
+
+#[test]
+fn cluster_administration_results_are_typed() {
+    use r::{
+        DbConfigOut, Expr, RebalanceResultOut, ReconfigureResultOut, SingleSelectionOut,
+        TableConfigOut, TableStatusOut, WaitResultOut,
+    };
+    let users = || r::db("test").table("users");
+    let table = serde_json::to_value(&users()).unwrap();
+
+    let _: Expr<SingleSelectionOut<DbConfigOut>, _> = r::db("test").config();
+    let _: Expr<ReconfigureResultOut, _> = users().reconfigure(1, 1);
+    let status: Expr<TableStatusOut, _> = users().status();
+    let wait: Expr<WaitResultOut, _> = users().wait().with_wait_for("ready_for_reads");
+    let rebalance: Expr<RebalanceResultOut, _> = users().rebalance();
+    assert_eq!(
+        serde_json::to_value(&(status, wait, rebalance)).unwrap(),
+        json!([
+            [STATUS, [table.clone()]],
+            [WAIT, [table.clone()], {"wait_for": "ready_for_reads"}],
+            [REBALANCE, [table.clone()]]
+        ])
+    );
+
+    let config: Expr<SingleSelectionOut<TableConfigOut>, _> = users().config();
+    let soft = config.update(r::object((("durability", "soft"),)));
+    assert_eq!(
+        serde_json::to_value(&soft).unwrap(),
+        json!([UPDATE, [[CONFIG, [table]], [OBJECT, ["durability", "soft"]]]])
+    );
+}
@@ -0,0 +1,200 @@
+//! A scripted stand-in for a RethinkDB server: accepts one connection, runs the V1_0 handshake
+//! for `admin` with an empty password, then lets a test read the client's queries and write
+//! responses.
+
+#![allow(dead_code)]
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use hmac::{Hmac, Mac};
+use pbkdf2::pbkdf2_hmac;
+use serde_json::{self, Value};
+use sha2::Sha256;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::thread::{self, JoinHandle};
+
+pub const START: u64 = 1;
+pub const CONTINUE: u64 = 2;
+pub const STOP: u64 = 3;
+
+pub const SUCCESS_ATOM: u64 = 1;
+pub const SUCCESS_SEQUENCE: u64 = 2;
+pub const SUCCESS_PARTIAL: u64 = 3;
+
+/// One query frame sent by the client.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    pub token: u64,
+    pub query_type: u64,
+    pub query: Value,
+}
+
+/// The server side of a connection, after the handshake.
+pub struct Peer<StreamT> {
+    stream: BufReader<StreamT>,
+}
+
+impl<StreamT: Read + Write> Peer<StreamT> {
+    /// Reads the next query frame, or `None` once the client has hung up.
+    pub fn read(&mut self) -> Option<Frame> {
+        let mut header = [0u8; 12];
+        match self.stream.read_exact(&mut header) {
+            Ok(()) => {}
+            Err(ref error) if error.kind() == io::ErrorKind::UnexpectedEof => return None,
+            Err(error) => panic!("failed to read query header: {}", error),
+        }
+        let mut body = vec![0u8; LittleEndian::read_u32(&header[8..]) as usize];
+        self.stream.read_exact(&mut body).unwrap();
+        let query: Value = serde_json::from_slice(&body).unwrap();
+        Some(Frame {
+            token: BigEndian::read_u64(&header[..8]),
+            query_type: query[0].as_u64().unwrap(),
+            query,
+        })
+    }
+
+    /// Reads the next query frame, which must exist and be of the given type.
+    pub fn expect(&mut self, query_type: u64) -> Frame {
+        let frame = self.read().expect("client hung up");
+        assert_eq!(frame.query_type, query_type, "unexpected frame: {:?}", frame);
+        frame
+    }
+
+    /// Reads every remaining frame, until the client hangs up.
+    pub fn drain(&mut self) -> Vec<Frame> {
+        let mut frames = Vec::new();
+        while let Some(frame) = self.read() {
+            frames.push(frame);
+        }
+        frames
+    }
+
+    /// Sends a response of the given type to `token`. Like the real server, `t` comes first.
+    pub fn respond(&mut self, token: u64, response_type: u64, documents: Value) {
+        let body = format!("{{\"t\":{},\"r\":{}}}", response_type, documents).into_bytes();
+        let mut header = [0u8; 12];
+        BigEndian::write_u64(&mut header[..8], token);
+        LittleEndian::write_u32(&mut header[8..], body.len() as u32);
+        let stream = self.stream.get_mut();
+        stream.write_all(&header).unwrap();
+        stream.write_all(&body).unwrap();
+        stream.flush().unwrap();
+    }
+}
+
+/// Listens on a free local port and runs `script` against the first client, over the stream
+/// returned by `wrap` (e.g. a TLS acceptor). Returns the address and the script's thread.
+pub fn serve_with<StreamT, WrapT, ScriptT, ResultT>(
+    wrap: WrapT,
+    script: ScriptT,
+) -> (SocketAddr, JoinHandle<ResultT>)
+where
+    StreamT: Read + Write,
+    WrapT: FnOnce(TcpStream) -> Option<StreamT> + Send + 'static,
+    ScriptT: FnOnce(&mut Peer<StreamT>) -> ResultT + Send + 'static,
+    ResultT: Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = thread::spawn(move || {
+        let (tcp, _) = listener.accept().unwrap();
+        let stream = wrap(tcp).expect("failed to set up the server stream");
+        let mut peer = Peer {
+            stream: BufReader::new(stream),
+        };
+        handshake(&mut peer.stream);
+        script(&mut peer)
+    });
+    (address, server)
+}
+
+/// Like `serve_with`, over plain TCP.
+pub fn serve<ScriptT, ResultT>(script: ScriptT) -> (SocketAddr, JoinHandle<ResultT>)
+where
+    ScriptT: FnOnce(&mut Peer<TcpStream>) -> ResultT + Send + 'static,
+    ResultT: Send + 'static,
+{
+    serve_with(Some, script)
+}
+
+fn handshake<StreamT: Read + Write>(stream: &mut BufReader<StreamT>) {
+    let mut magic = [0u8; 4];
+    stream.read_exact(&mut magic).unwrap();
+    assert_eq!(LittleEndian::read_u32(&magic), 0x34c2_bdc3, "not a V1_0 handshake");
+    send_message(
+        stream,
+        &json!({
+            "success": true,
+            "min_protocol_version": 0,
+            "max_protocol_version": 0,
+            "server_version": "2.3.0",
+        }),
+    );
+
+    let client_first = read_message(stream);
+    let client_first = client_first["authentication"].as_str().unwrap();
+    let client_first_bare = &client_first[3..];
+    let client_nonce = attribute(client_first_bare, "r");
+    let salt = b"stand-in salt";
+    let server_first = format!(
+        "r={}server-nonce,s={},i={}",
+        client_nonce,
+        BASE64.encode(salt),
+        ITERATIONS
+    );
+    send_message(
+        stream,
+        &json!({ "success": true, "authentication": server_first }),
+    );
+
+    let client_final = read_message(stream);
+    let client_final = client_final["authentication"].as_str().unwrap();
+    let client_final_without_proof = &client_final[..client_final.find(",p=").unwrap()];
+    let auth_message = format!(
+        "{},{},{}",
+        client_first_bare, server_first, client_final_without_proof
+    );
+    let mut salted_password = [0u8; 32];
+    pbkdf2_hmac::<Sha256>(b"", salt, ITERATIONS, &mut salted_password);
+    let server_key = hmac(&salted_password, b"Server Key");
+    let server_signature = hmac(&server_key, auth_message.as_bytes());
+    send_message(
+        stream,
+        &json!({
+            "success": true,
+            "authentication": format!("v={}", BASE64.encode(&server_signature)),
+        }),
+    );
+}
+
+fn attribute<'a>(message: &'a str, name: &str) -> &'a str {
+    message
+        .split(',')
+        .find(|attribute| attribute.starts_with(name) && attribute[name.len()..].starts_with('='))
+        .map(|attribute| &attribute[name.len() + 1..])
+        .unwrap()
+}
+
+fn hmac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).unwrap();
+    mac.update(message);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn read_message<StreamT: Read + Write>(stream: &mut BufReader<StreamT>) -> Value {
+    let mut message = Vec::new();
+    stream.read_until(0, &mut message).unwrap();
+    assert_eq!(message.pop(), Some(0), "handshake message not terminated");
+    serde_json::from_slice(&message).unwrap()
+}
+
+fn send_message<StreamT: Read + Write>(stream: &mut BufReader<StreamT>, message: &Value) {
+    let mut buffer = serde_json::to_vec(message).unwrap();
+    buffer.push(0);
+    stream.get_mut().write_all(&buffer).unwrap();
+    stream.get_mut().flush().unwrap();
+}
+
+const ITERATIONS: u32 = 64;
@@ -0,0 +1,68 @@
+#![cfg(feature = "tls")]
+
+extern crate base64;
+extern crate byteorder;
+extern crate hmac;
+extern crate native_tls;
+extern crate pbkdf2;
+extern crate rethink;
+#[macro_use]
+extern crate serde_json;
+extern crate sha2;
+
+mod support;
+
+use native_tls::{Identity, TlsAcceptor};
+use rethink::{ConnectOptions, Connection, ErrorKind, RawConnection, TlsOptions, Wait};
+use std::net::TcpListener;
+use std::thread;
+
+// A self-signed certificate for `localhost`, valid until 2126; the PKCS#12 password is `rethink`.
+const CERTIFICATE_PEM: &[u8] = include_bytes!("data/localhost.pem");
+const IDENTITY_PKCS12: &[u8] = include_bytes!("data/localhost.p12");
+
+fn acceptor() -> TlsAcceptor {
+    TlsAcceptor::new(Identity::from_pkcs12(IDENTITY_PKCS12, "rethink").unwrap()).unwrap()
+}
+
+#[test]
+fn connects_with_trusted_ca() {
+    let acceptor = acceptor();
+    let (address, server) = support::serve_with(
+        move |tcp| acceptor.accept(tcp).ok(),
+        |peer| {
+            let start = peer.expect(support::START);
+            peer.respond(start.token, support::SUCCESS_ATOM, json!(["over tls"]));
+        },
+    );
+
+    let options =
+        ConnectOptions::new(address).tls(TlsOptions::new("localhost").ca_bundle(CERTIFICATE_PEM));
+    let mut connection = Connection::from_raw(RawConnection::connect(options).unwrap());
+    let mut cursor = connection.run(json!("over tls")).unwrap();
+    assert_eq!(
+        connection.next(Wait::Yes, &mut cursor).unwrap(),
+        Some("over tls".to_owned())
+    );
+    connection.close().unwrap();
+    server.join().unwrap();
+}
+
+#[test]
+fn rejects_untrusted_certificate() {
+    let acceptor = acceptor();
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap();
+    let server = thread::spawn(move || {
+        let (tcp, _) = listener.accept().unwrap();
+        acceptor.accept(tcp).is_err()
+    });
+
+    let options = ConnectOptions::new(address).tls(TlsOptions::new("localhost"));
+    let error = RawConnection::connect(options).err().expect("connected to an untrusted server");
+    match *error.kind() {
+        ErrorKind::Tls(_) => {}
+        ref kind => panic!("unexpected error: {}", kind),
+    }
+    assert!(server.join().unwrap(), "server completed the TLS handshake");
+}
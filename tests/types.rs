@@ -0,0 +1,344 @@
+#[cfg(feature = "chrono")]
+extern crate chrono;
+extern crate rethink;
+#[macro_use]
+extern crate serde_json;
+
+#[cfg(feature = "chrono")]
+use chrono::{FixedOffset, TimeZone, Utc};
+
+use rethink::types::{
+    Change, ChangeType, DbConfig, FeedState, Geometry, GroupedData, IndexStatus, Join, Point,
+    RebalanceResult, ReconfigureResult, RegexGroup, RegexMatch, ReplicaState, ReqlBinary,
+    ReqlTime, TableConfig, TableStatus, WaitResult, WriteResult,
+};
+use std::collections::BTreeMap;
+
+#[test]
+fn index_status_deserializes_from_a_server_payload() {
+    let statuses: Vec<IndexStatus> = serde_json::from_str(
+        r#"[
+            {
+                "function": {"$reql_type$": "BINARY", "data": "JHJlcWxfdHlwZSQ="},
+                "geo": false,
+                "index": "tags",
+                "multi": true,
+                "outdated": false,
+                "progress": 0.25,
+                "query": "indexCreate('tags', function(var1) { return r.row('tags'); }, {multi: true})",
+                "ready": false
+            },
+            {
+                "function": {"$reql_type$": "BINARY", "data": ""},
+                "geo": false,
+                "index": "name",
+                "multi": false,
+                "outdated": false,
+                "query": "indexCreate('name', function(var1) { return var1('name'); })",
+                "ready": true
+            }
+        ]"#,
+    ).unwrap();
+
+    assert_eq!(statuses[0].index, "tags");
+    assert_eq!(statuses[0].function, ReqlBinary(b"$reql_type$".to_vec()));
+    assert_eq!(statuses[0].progress, Some(0.25));
+    assert!(statuses[0].multi && !statuses[0].ready);
+    assert_eq!(statuses[1].progress, None);
+    assert_eq!(statuses[1].function, ReqlBinary(Vec::new()));
+    assert!(statuses[1].ready);
+}
+
+#[test]
+fn table_status_deserializes_from_a_server_payload() {
+    let status: TableStatus = serde_json::from_str(include_str!("data/status.json")).unwrap();
+
+    assert_eq!(status.name, "users");
+    assert_eq!(status.raft_leader, Some("alpha".to_owned()));
+    assert!(status.status.ready_for_writes && !status.status.all_replicas_ready);
+    let shards = status.shards.unwrap();
+    assert_eq!(shards[0].primary_replicas, vec!["alpha".to_owned()]);
+    assert_eq!(shards[0].replicas[0].state, ReplicaState::Ready);
+    assert_eq!(shards[0].replicas[1].server, "beta");
+    assert_eq!(shards[0].replicas[1].state, ReplicaState::Backfilling);
+}
+
+#[test]
+fn dry_run_reconfigure_result_deserializes_without_status_changes() {
+    let result: ReconfigureResult =
+        serde_json::from_str(include_str!("data/reconfigure_dry_run.json")).unwrap();
+
+    assert_eq!(result.reconfigured, 0);
+    assert_eq!(result.repaired, 0);
+    assert!(result.status_changes.is_empty());
+    let change = &result.config_changes[0];
+    assert_eq!(change.old_val.as_ref().unwrap().shards.len(), 1);
+    let new_config = change.new_val.as_ref().unwrap();
+    assert_eq!(new_config.shards.len(), 2);
+    assert_eq!(new_config.shards[1].primary_replica, "beta");
+    assert_eq!(new_config.write_acks, "majority");
+}
+
+#[test]
+fn rebalance_result_deserializes_from_a_server_payload() {
+    let result: RebalanceResult =
+        serde_json::from_str(include_str!("data/rebalance.json")).unwrap();
+
+    assert_eq!(result.rebalanced, 1);
+    let change = &result.status_changes[0];
+    assert!(change.old_val.as_ref().unwrap().status.all_replicas_ready);
+    let new_status = change.new_val.as_ref().unwrap();
+    assert!(!new_status.status.ready_for_outdated_reads);
+    let shards = new_status.shards.as_ref().unwrap();
+    assert!(shards[0].primary_replicas.is_empty());
+    assert_eq!(shards[0].replicas[0].state, ReplicaState::Transitioning);
+}
+
+#[test]
+fn changes_deserialize_with_types_and_states() {
+    let changes: Vec<Change<serde_json::Value>> = serde_json::from_str(
+        r#"[
+            {"state": "initializing", "type": "state"},
+            {"new_val": {"id": "ada"}, "type": "initial"},
+            {"state": "ready", "type": "state"},
+            {"new_val": {"id": "bob"}, "old_val": null, "type": "add"},
+            {"new_val": {"id": "bob", "age": 3}, "old_val": {"id": "bob"}, "type": "change"},
+            {"new_val": null, "old_val": {"id": "ada"}, "type": "remove"},
+            {"new_val": {"id": "eve"}, "old_val": null}
+        ]"#,
+    ).unwrap();
+
+    assert_eq!(changes[0].state, Some(FeedState::Initializing));
+    assert_eq!(changes[0].change_type, Some(ChangeType::State));
+    assert_eq!((&changes[0].old_val, &changes[0].new_val), (&None, &None));
+    assert_eq!(changes[1].change_type, Some(ChangeType::Initial));
+    assert_eq!(changes[1].new_val.as_ref().unwrap()["id"], "ada");
+    assert_eq!(changes[2].state, Some(FeedState::Ready));
+    assert_eq!(changes[3].change_type, Some(ChangeType::Add));
+    assert!(changes[3].old_val.is_none());
+    assert_eq!(changes[4].old_val.as_ref().unwrap()["id"], "bob");
+    assert_eq!(changes[4].new_val.as_ref().unwrap()["age"], 3);
+    assert_eq!(changes[5].change_type, Some(ChangeType::Remove));
+    assert!(changes[5].new_val.is_none());
+    assert_eq!(changes[6].change_type, None);
+    assert_eq!(changes[6].state, None);
+}
+
+#[test]
+fn grouped_data_keeps_the_server_order() {
+    let grouped: GroupedData<String, u64> = serde_json::from_str(
+        r#"{"$reql_type$": "GROUPED_DATA", "data": [["red", 2], ["blue", 5]]}"#,
+    ).unwrap();
+
+    assert_eq!(grouped.len(), 2);
+    assert_eq!(grouped.get(&"blue".to_owned()), Some(&5));
+    assert_eq!(grouped.get(&"green".to_owned()), None);
+    assert_eq!(
+        grouped.iter().map(|&(ref key, _)| &key[..]).collect::<Vec<_>>(),
+        vec!["red", "blue"]
+    );
+    let by_key: BTreeMap<String, u64> = grouped.into();
+    assert_eq!(by_key["red"], 2);
+}
+
+#[test]
+fn grouped_data_rejects_other_pseudo_types() {
+    let error = serde_json::from_str::<GroupedData<String, u64>>(
+        r#"{"$reql_type$": "TIME", "data": []}"#,
+    ).unwrap_err();
+    assert!(error.to_string().contains("GROUPED_DATA"));
+}
+
+#[test]
+fn outer_join_pairs_deserialize_with_a_missing_right() {
+    let pairs: Vec<Join<u64, Option<u64>>> =
+        serde_json::from_str(r#"[{"left": 1}, {"left": 2, "right": 2}]"#).unwrap();
+    assert_eq!(pairs[0], Join { left: 1, right: None });
+    assert_eq!(pairs[1], Join { left: 2, right: Some(2) });
+}
+
+#[test]
+fn reql_time_round_trips_as_a_pseudo_type() {
+    let time = ReqlTime::new(-86400.25, "-08:00");
+    let serialized = serde_json::to_value(&time).unwrap();
+    assert_eq!(
+        serialized,
+        json!({"$reql_type$": "TIME", "epoch_time": -86400.25, "timezone": "-08:00"})
+    );
+    assert_eq!(serde_json::from_value::<ReqlTime>(serialized).unwrap(), time);
+
+    let error = serde_json::from_value::<ReqlTime>(
+        json!({"$reql_type$": "GEOMETRY", "epoch_time": 0, "timezone": "+00:00"}),
+    ).unwrap_err();
+    assert!(error.to_string().contains("expected TIME"));
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn reql_time_converts_to_and_from_chrono() {
+    let offset = FixedOffset::west_opt(3 * 3600 + 30 * 60).unwrap();
+    let before_epoch = offset.with_ymd_and_hms(1969, 7, 20, 17, 17, 40).unwrap()
+        + chrono::Duration::milliseconds(250);
+    let time = ReqlTime::from(before_epoch);
+    assert_eq!(time.timezone, "-03:30");
+    assert_eq!(time.epoch_time, -14_181_139.75);
+    assert_eq!(time.to_date_time(), Some(before_epoch));
+
+    // Sub-millisecond precision is rounded away, as the server only keeps milliseconds.
+    let rounded = ReqlTime::utc(1.2346).to_date_time().unwrap();
+    assert_eq!(rounded, Utc.timestamp_millis_opt(1235).unwrap());
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn reql_time_parses_zulu_and_rejects_malformed_timezones() {
+    let zulu = ReqlTime::new(0.0, "Z").to_date_time().unwrap();
+    assert_eq!(zulu.offset().local_minus_utc(), 0);
+
+    for timezone in &["", "08:00", "+8h", "+08:xx", "+99:00"] {
+        assert_eq!(ReqlTime::new(0.0, *timezone).to_date_time(), None, "{}", timezone);
+    }
+}
+
+#[test]
+fn geometry_round_trips_as_geojson() {
+    let polygon = Geometry::Polygon(vec![vec![
+        Point(0.0, 0.0),
+        Point(1.0, 0.0),
+        Point(1.0, 1.0),
+        Point(0.0, 0.0),
+    ]]);
+    let serialized = serde_json::to_value(&polygon).unwrap();
+    assert_eq!(
+        serialized,
+        json!({
+            "$reql_type$": "GEOMETRY",
+            "type": "Polygon",
+            "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]
+        })
+    );
+    assert_eq!(serde_json::from_value::<Geometry>(serialized).unwrap(), polygon);
+
+    let point: Geometry = serde_json::from_value(
+        json!({"$reql_type$": "GEOMETRY", "type": "Point", "coordinates": [-122.4, 37.8]}),
+    ).unwrap();
+    assert_eq!(point, Geometry::Point(Point(-122.4, 37.8)));
+
+    assert!(serde_json::from_value::<Geometry>(
+        json!({"$reql_type$": "TIME", "type": "Point", "coordinates": [0, 0]})
+    ).is_err());
+}
+
+#[test]
+fn reql_binary_round_trips_as_base64() {
+    let binary = ReqlBinary(b"\x00rethink\xff".to_vec());
+    let serialized = serde_json::to_value(&binary).unwrap();
+    assert_eq!(
+        serialized,
+        json!({"$reql_type$": "BINARY", "data": "AHJldGhpbmv/"})
+    );
+    assert_eq!(serde_json::from_value::<ReqlBinary>(serialized).unwrap(), binary);
+
+    let wrapped: ReqlBinary = serde_json::from_value(
+        json!({"$reql_type$": "BINARY", "data": "AHJl\ndGhp\nbmv/"}),
+    ).unwrap();
+    assert_eq!(wrapped, binary);
+}
+
+#[test]
+fn reql_binary_rejects_bad_base64_and_other_pseudo_types() {
+    let error = serde_json::from_value::<ReqlBinary>(
+        json!({"$reql_type$": "BINARY", "data": "not base64!"}),
+    ).unwrap_err();
+    assert!(error.to_string().contains("invalid base64"));
+
+    let error = serde_json::from_value::<ReqlBinary>(
+        json!({"$reql_type$": "TIME", "data": ""}),
+    ).unwrap_err();
+    assert!(error.to_string().contains("expected BINARY"));
+}
+
+#[test]
+fn regex_match_deserializes_unmatched_groups_as_none() {
+    let matched: RegexMatch = serde_json::from_str(
+        r#"{
+            "str": "ada@example",
+            "start": 0,
+            "end": 11,
+            "groups": [{"str": "ada", "start": 0, "end": 3}, null]
+        }"#,
+    ).unwrap();
+
+    assert_eq!((matched.start, matched.end), (0, 11));
+    assert_eq!(
+        matched.groups,
+        vec![
+            Some(RegexGroup {
+                str: "ada".to_owned(),
+                start: 0,
+                end: 3,
+            }),
+            None,
+        ]
+    );
+}
+
+#[test]
+fn write_results_default_missing_counters() {
+    let bare: WriteResult<serde_json::Value> =
+        serde_json::from_str(r#"{"inserted": 1}"#).unwrap();
+    assert_eq!(bare.inserted, 1);
+    assert_eq!((bare.replaced, bare.errors), (0, 0));
+    assert_eq!(bare.first_error, None);
+    assert!(bare.generated_keys.is_empty() && bare.changes.is_empty());
+
+    let generated: WriteResult<serde_json::Value> = serde_json::from_str(
+        r#"{"inserted": 2, "errors": 1, "first_error": "Duplicate primary key",
+            "generated_keys": ["8a1b", "c2d3"], "warnings": ["too many keys"]}"#,
+    ).unwrap();
+    assert_eq!(generated.generated_keys, vec!["8a1b", "c2d3"]);
+    assert_eq!(
+        generated.first_error,
+        Some("Duplicate primary key".to_owned())
+    );
+    assert_eq!(generated.warnings.len(), 1);
+}
+
+#[test]
+fn write_results_carry_typed_changes() {
+    let result: WriteResult<BTreeMap<String, u64>> = serde_json::from_str(
+        r#"{
+            "replaced": 1,
+            "changes": [{"new_val": {"age": 37}, "old_val": {"age": 36}}]
+        }"#,
+    ).unwrap();
+
+    assert_eq!(result.replaced, 1);
+    assert_eq!(result.changes.len(), 1);
+    assert_eq!(result.changes[0].old_val.as_ref().unwrap()["age"], 36);
+    assert_eq!(result.changes[0].new_val.as_ref().unwrap()["age"], 37);
+}
+
+#[test]
+fn configs_and_wait_results_deserialize_from_server_payloads() {
+    let db: DbConfig = serde_json::from_str(r#"{"id": "7c3d", "name": "test"}"#).unwrap();
+    assert_eq!(db.name, "test");
+
+    let table: TableConfig = serde_json::from_str(
+        r#"{
+            "id": "31c9",
+            "name": "users",
+            "db": "test",
+            "primary_key": "id",
+            "shards": [{"primary_replica": "alpha", "replicas": ["alpha", "beta"]}],
+            "indexes": ["email"],
+            "write_acks": "majority",
+            "durability": "hard"
+        }"#,
+    ).unwrap();
+    assert_eq!(table.shards[0].replicas, vec!["alpha", "beta"]);
+    assert!(table.shards[0].nonvoting_replicas.is_empty());
+
+    let wait: WaitResult = serde_json::from_str(r#"{"ready": 1}"#).unwrap();
+    assert_eq!(wait, WaitResult { ready: 1 });
+}